@@ -0,0 +1,94 @@
+use crate::game::GameState;
+use crate::wordlist::added_letters;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// What a finished puzzle looked like, kept so later visits still show the trophy
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Completion {
+    /// Play from the first find to the last, with long pauses cut short, when the first was timed
+    pub seconds: Option<u64>,
+    pub words: usize,
+    pub points: usize,
+    pub longest: String,
+    /// The outside letter the fewest answers add, and how many do
+    pub rarest: Option<(char, usize)>,
+    /// Hint tokens spent along the way
+    #[serde(default)]
+    pub hints: usize,
+}
+
+impl Completion {
+    pub fn new(game: &GameState, seconds: Option<u64>) -> Completion {
+        let words = &game.wordlist().words;
+        let longest = words
+            .iter()
+            .max_by(|a, b| a.len().cmp(&b.len()).then_with(|| b.cmp(a)))
+            .cloned()
+            .unwrap_or_default();
+        let grid = game.grid();
+        let mut added = BTreeMap::<char, usize>::new();
+        for letter in words.iter().flat_map(|word| added_letters(&grid, word)) {
+            *added.entry(letter).or_insert(0) += 1;
+        }
+        let rarest = added.into_iter().min_by_key(|(_, count)| *count);
+        Completion {
+            seconds,
+            words: words.len(),
+            points: game.max_score(),
+            longest,
+            rarest,
+            hints: game.hints().len(),
+        }
+    }
+
+    /// `m:ss`, or `h:mm:ss` past the hour
+    pub fn time(&self) -> Option<String> {
+        let seconds = self.seconds?;
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        Some(if hours > 0 {
+            format!("{}:{:02}:{:02}", hours, minutes, seconds)
+        } else {
+            format!("{}:{:02}", minutes, seconds)
+        })
+    }
+
+    /// A spoiler-free line to paste elsewhere
+    pub fn share_text(&self, puzzle: &str) -> String {
+        let mut text = format!("Emily's Bee {}: Queen Bee! {} words, {} points", puzzle, self.words, self.points);
+        if let Some(time) = self.time() {
+            text.push_str(&format!(" in {}", time));
+        }
+        match self.hints {
+            0 => (),
+            1 => text.push_str(" with 1 hint"),
+            hints => text.push_str(&format!(" with {} hints", hints)),
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::completion::Completion;
+    use crate::wordlist::sample;
+    use crate::GameState;
+
+    #[test]
+    fn summarises_the_puzzle() {
+        let wordlist = sample(&["lockage", "smocake", "gamecock", "cockamamie"]);
+        let words = wordlist.words.clone();
+        let completion = Completion::new(&GameState::new(wordlist, words, false), Some(3725));
+        assert_eq!(completion.longest, "cockamamie");
+        // Ties go to the earliest letter
+        assert_eq!(completion.rarest, Some(('i', 1)));
+        assert_eq!(completion.points, 32);
+        assert_eq!(completion.time().as_deref(), Some("1:02:05"));
+        assert_eq!(
+            completion.share_text("2022-03-07"),
+            "Emily's Bee 2022-03-07: Queen Bee! 4 words, 32 points in 1:02:05"
+        );
+        let hinted = Completion { hints: 2, seconds: None, ..completion };
+        assert_eq!(hinted.share_text("practice"), "Emily's Bee practice: Queen Bee! 4 words, 32 points with 2 hints");
+    }
+}
@@ -0,0 +1,105 @@
+use crate::wordlist::Wordlist;
+use std::collections::HashMap;
+
+/// Short definitions read from a dictionary on disk, to attach to a puzzle's answers
+#[derive(Default)]
+pub struct Definitions {
+    words: HashMap<String, String>,
+}
+
+impl Definitions {
+    /// Adds the senses in a WordNet `data.*` file. Each line is a synset: its words after a
+    /// two-digit hex count, then the gloss after a `|`. A word keeps the first gloss it's seen
+    /// with, cut down to the definition before any example sentences.
+    pub fn add_wordnet(&mut self, data: &str) {
+        for line in data.lines() {
+            // The licence at the top of each file is indented
+            if line.starts_with(' ') {
+                continue;
+            }
+            let (synset, gloss) = match line.split_once(" | ") {
+                Some(parts) => parts,
+                None => continue,
+            };
+            let definition = gloss.split(';').next().unwrap_or_default().trim();
+            let mut fields = synset.split(' ').skip(3);
+            let count = fields.next().and_then(|count| usize::from_str_radix(count, 16).ok()).unwrap_or(0);
+            // Each word is followed by its lexical id
+            for word in fields.step_by(2).take(count) {
+                self.add(word, definition);
+            }
+        }
+    }
+
+    /// Adds one definition per line, as `word<tab>definition`
+    pub fn add_tsv(&mut self, tsv: &str) {
+        for line in tsv.lines() {
+            if let Some((word, definition)) = line.split_once('\t') {
+                self.add(word, definition.trim());
+            }
+        }
+    }
+
+    fn add(&mut self, word: &str, definition: &str) {
+        // Answers are single lowercase words; adjectives can carry a marker like `(a)`
+        let word = word.split('(').next().unwrap_or_default();
+        if definition.is_empty() || !word.chars().all(|c| c.is_ascii_alphabetic()) {
+            return;
+        }
+        self.words.entry(word.to_ascii_lowercase()).or_insert_with(|| definition.to_string());
+    }
+
+    pub fn get(&self, word: &str) -> Option<&str> {
+        self.words.get(word).map(|definition| definition.as_str())
+    }
+
+    /// Fills in the definition of every answer that has one, returning how many do
+    pub fn attach(&self, wordlist: &mut Wordlist) -> usize {
+        let mut attached = 0;
+        for word in &wordlist.words {
+            let (info, definition) = match (wordlist.metadata.get_mut(word), self.get(word)) {
+                (Some(info), Some(definition)) => (info, definition),
+                _ => continue,
+            };
+            info.definition = Some(definition.to_string());
+            attached += 1;
+        }
+        attached
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::definitions::Definitions;
+    use crate::Dictionary;
+
+    const DATA: &str = "  1 This software and database is being provided to you, the LICENSEE, by
+01792158 05 n 02 gamecock 0 fighting_cock 0 001 @ 01791625 n 0000 | a cock bred and trained for fighting; \"the gamecock crowed\"
+01823370 05 n 01 gamecock 1 000 | a second sense that shouldn't win
+00123456 00 s 01 cockamamie(a) 0 000 | ludicrously odd; \"Cockamamie ideas\"
+";
+
+    #[test]
+    fn reads_wordnet_glosses() {
+        let mut definitions = Definitions::default();
+        definitions.add_wordnet(DATA);
+        assert_eq!(definitions.get("gamecock"), Some("a cock bred and trained for fighting"));
+        assert_eq!(definitions.get("cockamamie"), Some("ludicrously odd"));
+        // Multi-word entries can't be answers
+        assert_eq!(definitions.get("fighting_cock"), None);
+        assert_eq!(definitions.get("this"), None);
+    }
+
+    #[test]
+    fn attaches_to_answers() {
+        let mut definitions = Definitions::default();
+        definitions.add_wordnet(DATA);
+        definitions.add_tsv("lockage\ta toll paid for passing through a lock\n");
+        let mut dictionary = Dictionary::default();
+        dictionary.add_level(10, "cockamamie\nlockage\nsmocake\n");
+        let mut wordlist = dictionary.solve('c', "gameok");
+        assert_eq!(definitions.attach(&mut wordlist), 2);
+        assert_eq!(wordlist.definition("lockage"), Some("a toll paid for passing through a lock"));
+        assert_eq!(wordlist.definition("smocake"), None);
+    }
+}
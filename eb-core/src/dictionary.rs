@@ -0,0 +1,263 @@
+use crate::rule::{LetterRule, Rule};
+use crate::wordlist::{WordInfo, Wordlist};
+use rand::prelude::{IteratorRandom, SliceRandom};
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+
+/// Letters in a hive, and so distinct letters in every answer
+pub const HIVE_SIZE: usize = 7;
+
+/// Whether `possible` is an answer for the hive `sb_word` by [`LetterRule::EMILY`]: it keeps the
+/// center, leaves out exactly one hive letter and brings in exactly one new one.
+pub fn is_emily_word_for(sb_word: &HashSet<char>, center_letter: char, possible: impl Iterator<Item=char>) -> bool {
+    LetterRule::EMILY.accepts(sb_word, center_letter, &possible.collect::<String>())
+}
+
+/// How obscure a generated puzzle's answers are allowed to be, on average
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        Difficulty::ALL.iter().copied().find(|difficulty| difficulty.name() == name)
+    }
+
+    /// Range of mean answer obscurity that counts as this difficulty. Most answers come from
+    /// the level 35 and 50 lists, so generated hives mostly land between 33 and 41; the bands
+    /// split that into rough thirds.
+    fn accepts(&self, obscurity: f64) -> bool {
+        match self {
+            Difficulty::Easy => obscurity < 35.0,
+            Difficulty::Medium => (35.0..38.5).contains(&obscurity),
+            Difficulty::Hard => obscurity >= 38.5,
+        }
+    }
+}
+
+/// Mean obscurity of a puzzle's answers; `None` when it has none or they carry no metadata
+pub fn mean_obscurity(wordlist: &Wordlist) -> Option<f64> {
+    let levels = wordlist
+        .words
+        .iter()
+        .filter_map(|word| wordlist.metadata.get(word))
+        .map(|info| info.obscurity as f64)
+        .collect::<Vec<_>>();
+    if levels.is_empty() {
+        return None;
+    }
+    Some(levels.iter().sum::<f64>() / levels.len() as f64)
+}
+
+/// Hives tried before [`Dictionary::generate`] gives up
+const GENERATE_ATTEMPTS: usize = 500;
+
+/// One bit per letter of the alphabet
+fn letter_mask(letters: impl Iterator<Item=char>) -> u32 {
+    letters
+        .filter(|c| c.is_ascii_lowercase())
+        .fold(0, |mask, c| mask | 1 << (c as u8 - b'a'))
+}
+
+/// Candidate answers, each with the obscurity level of the SCOWL list it came from
+#[derive(Default)]
+pub struct Dictionary {
+    words: Vec<(String, usize)>,
+}
+
+impl Dictionary {
+    /// Adds the words of one SCOWL list, skipping anything with punctuation in it
+    pub fn add_level(&mut self, obscurity: usize, list: &str) {
+        for word in list.lines() {
+            if !word.chars().all(|c| c.is_alphabetic()) {
+                continue;
+            }
+            self.words.push((word.trim().to_string(), obscurity));
+        }
+    }
+
+    /// Reads the format written by [`Dictionary::to_compact`]
+    pub fn parse(compact: &str) -> Dictionary {
+        let mut words = vec![];
+        let mut obscurity = 0;
+        for line in compact.lines() {
+            match line.strip_prefix('#') {
+                Some(level) => obscurity = level.parse().unwrap_or(obscurity),
+                None if !line.is_empty() => words.push((line.to_string(), obscurity)),
+                None => (),
+            }
+        }
+        Dictionary { words }
+    }
+
+    /// Just the words with seven distinct letters, one per line under a `#<obscurity>` heading
+    /// per level; small enough to bundle into the web app. Those are every answer under rules
+    /// that drop as many letters as they add, but only the pangrams under the classic rule.
+    pub fn to_compact(&self) -> String {
+        let mut compact = String::new();
+        let mut level = None;
+        for (word, obscurity) in &self.words {
+            let distinct = word.chars().collect::<HashSet<_>>();
+            if distinct.len() != HIVE_SIZE || !word.chars().all(|c| c.is_ascii_lowercase()) {
+                continue;
+            }
+            if level != Some(*obscurity) {
+                compact.push_str(&format!("#{}\n", obscurity));
+                level = Some(*obscurity);
+            }
+            compact.push_str(word);
+            compact.push('\n');
+        }
+        compact
+    }
+
+    /// Every answer for the hive, in dictionary order
+    pub fn solve(&self, center: char, outer: &str) -> Wordlist {
+        self.solve_with(center, outer, LetterRule::EMILY)
+    }
+
+    /// Every answer for the hive by `rule`, with pangrams flagged where the rule allows them
+    pub fn solve_with(&self, center: char, outer: &str, rule: LetterRule) -> Wordlist {
+        let mut hive = outer.chars().collect::<HashSet<_>>();
+        hive.insert(center);
+        let mut words = vec![];
+        let mut metadata = HashMap::new();
+        for (word, obscurity) in &self.words {
+            if rule.accepts(&hive, center, word) && !metadata.contains_key(word) {
+                words.push(word.clone());
+                let pangram = hive.iter().all(|letter| word.contains(*letter));
+                metadata.insert(word.clone(), WordInfo { obscurity: *obscurity, definition: None, pangram });
+            }
+        }
+        Wordlist {
+            center,
+            outer: outer.to_string(),
+            words,
+            date: None,
+            metadata,
+            rule,
+            classic: None,
+        }
+    }
+
+    /// A random hive with at least `min_answers` answers by `rule` at the given difficulty.
+    ///
+    /// Hives are built backwards from a random answer: the rule's added letters are taken out
+    /// of it and as many dropped ones put in, so every attempt has at least one answer.
+    pub fn generate(&self, rng: &mut impl Rng, min_answers: usize, difficulty: Difficulty, rule: LetterRule) -> Option<Wordlist> {
+        // Answers have a known number of distinct letters under most rules, so only those
+        // words are worth checking, and comparing letter masks is much cheaper than calling
+        // `solve_with` per attempt. Seeds use as many letters as an answer can.
+        let seed_size = HIVE_SIZE - rule.dropped.unwrap_or(0) + rule.added;
+        let candidates = self
+            .words
+            .iter()
+            .filter(|(word, _)| word.chars().all(|c| c.is_ascii_lowercase()))
+            .map(|(word, obscurity)| (word, *obscurity, letter_mask(word.chars())))
+            .filter(|(_, _, mask)| match rule.distinct_letters() {
+                Some(size) => mask.count_ones() as usize == size,
+                None => mask.count_ones() as usize <= seed_size,
+            })
+            .collect::<Vec<_>>();
+        let seeds = candidates.iter().filter(|(_, _, mask)| mask.count_ones() as usize == seed_size).collect::<Vec<_>>();
+        for _ in 0..GENERATE_ATTEMPTS {
+            let (seed, _, _) = seeds.choose(rng)?;
+            let letters = seed.chars().collect::<HashSet<_>>();
+            let added = letters.iter().copied().choose_multiple(rng, rule.added);
+            let dropped = ('a'..='z').filter(|c| !letters.contains(c)).choose_multiple(rng, rule.dropped.unwrap_or(0));
+            let center = *letters.iter().filter(|c| !added.contains(c)).choose(rng)?;
+            let mut outer = letters
+                .iter()
+                .copied()
+                .filter(|c| !added.contains(c) && *c != center)
+                .chain(dropped)
+                .collect::<Vec<_>>();
+            outer.sort_unstable();
+            let hive = letter_mask(outer.iter().copied().chain(Some(center)));
+            let center_bit = letter_mask(Some(center).into_iter());
+            let answers = candidates
+                .iter()
+                .filter(|(_, _, mask)| !rule.center_required || mask & center_bit != 0)
+                .filter(|(_, _, mask)| (mask & !hive).count_ones() as usize == rule.added)
+                .filter(|(_, _, mask)| rule.dropped.is_none_or(|dropped| (hive & !mask).count_ones() as usize == dropped))
+                .count();
+            if answers < min_answers {
+                continue;
+            }
+            // Words listed at more than one level are only counted once by `solve_with`, which
+            // also holds them to the rule's length and repeat limits
+            let wordlist = self.solve_with(center, &outer.into_iter().collect::<String>(), rule);
+            let fits = mean_obscurity(&wordlist).is_some_and(|obscurity| difficulty.accepts(obscurity));
+            if wordlist.words.len() >= min_answers && fits {
+                return Some(wordlist);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dictionary::{is_emily_word_for, Difficulty, Dictionary};
+    use crate::rule::LetterRule;
+
+    #[test]
+    fn emily_words() {
+        let base = "gamecock".chars().collect();
+        assert!(is_emily_word_for(&base, 'c', "lockage".chars()));
+        assert!(!is_emily_word_for(&base, 'c', "bloop".chars()))
+    }
+
+    #[test]
+    fn classic_words() {
+        let mut dictionary = Dictionary::default();
+        dictionary.add_level(10, "cameo\ngamecock\nlockage\ncom\ngame\n");
+        let classic = dictionary.solve_with('c', "gameok", LetterRule::CLASSIC);
+        assert_eq!(classic.words, vec!["cameo", "gamecock"]);
+        assert!(classic.metadata["gamecock"].pangram);
+        assert_eq!((classic.points("cameo"), classic.points("gamecock")), (5, 15));
+    }
+
+    #[test]
+    fn compact_round_trip() {
+        let mut dictionary = Dictionary::default();
+        dictionary.add_level(10, "lockage\nbloop\nit's\n");
+        dictionary.add_level(20, "Lockage\nsmocake\n");
+        let compact = dictionary.to_compact();
+        assert_eq!(compact, "#10\nlockage\n#20\nsmocake\n");
+        let wordlist = Dictionary::parse(&compact).solve('c', "gameok");
+        assert_eq!(wordlist.words, vec!["lockage", "smocake"]);
+        assert_eq!(wordlist.metadata["smocake"].obscurity, 20);
+    }
+
+    #[test]
+    fn generated_hive_fits_request() {
+        let mut dictionary = Dictionary::default();
+        dictionary.add_level(10, "lockage\nsmocake\npartially\nupstairs\n");
+        let mut rng = rand::thread_rng();
+        let wordlist = dictionary.generate(&mut rng, 1, Difficulty::Easy, LetterRule::EMILY).unwrap();
+        assert_eq!(wordlist.outer.len(), 6);
+        assert!(!wordlist.words.is_empty());
+        assert!(wordlist.words.iter().all(|word| word.contains(wordlist.center)));
+        assert!(dictionary.generate(&mut rng, 5, Difficulty::Easy, LetterRule::EMILY).is_none());
+
+        dictionary.add_level(10, "smocked\n");
+        let squared = dictionary.generate(&mut rng, 1, Difficulty::Easy, LetterRule::EMILY_SQUARED).unwrap();
+        assert_eq!(squared.rule, LetterRule::EMILY_SQUARED);
+        assert_eq!(squared.outer.len(), 6);
+        assert!(!squared.words.is_empty());
+    }
+}
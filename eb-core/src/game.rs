@@ -0,0 +1,558 @@
+use crate::hints::{self, Hint, HintKind};
+use crate::timeline::{self, Find, Stamp};
+use crate::rule::Rule;
+use crate::wordlist::{added_letters, Wordlist};
+use rand::prelude::SliceRandom;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Everything a player can do to a puzzle
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    Push(char),
+    /// Replaces the word being typed, e.g. from a native text input
+    SetWord(String),
+    Backspace,
+    Submit,
+    Shuffle,
+    Reveal,
+    /// Spends a hint token on an unfound answer
+    Hint(HintKind),
+}
+
+/// What happened as a result of an [`Action`], for the front end to show or persist
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    Found(String),
+    /// Follows the `Found` that completes the puzzle, unless the answers were revealed first
+    Completed,
+    Rejected(Rejection),
+    Revealed,
+    Hinted(Hint),
+}
+
+pub type Events = Vec<Event>;
+
+/// Why a guess wasn't accepted
+#[derive(Clone, Debug, PartialEq)]
+pub enum Rejection {
+    TooManyNew,
+    /// Uses a letter from outside the hive, under a rule that doesn't add any
+    BadLetters,
+    TooShort,
+    MissingCenter,
+    RepeatedLetter,
+    Dropped(Vec<char>),
+    NotInWordlist,
+    AlreadyFound,
+}
+
+impl Rejection {
+    pub fn message(&self) -> String {
+        match self {
+            Rejection::TooManyNew => "Too many new letters".to_string(),
+            Rejection::BadLetters => "Bad letters".to_string(),
+            Rejection::TooShort => "Too short".to_string(),
+            Rejection::RepeatedLetter => "No repeated letters".to_string(),
+            Rejection::MissingCenter => "Missing center letter".to_string(),
+            Rejection::Dropped(missing) => format!(
+                "Too many hive letters left out. Missing: {:?}",
+                missing
+            ),
+            Rejection::NotInWordlist => "Not in wordlist".to_string(),
+            Rejection::AlreadyFound => "Already found".to_string(),
+        }
+    }
+}
+
+/// Rank names and the share of the puzzle's points each needs, lowest first
+pub const RANKS: [(&str, f64); 10] = [
+    ("Beginner", 0.0),
+    ("Good Start", 0.02),
+    ("Moving Up", 0.05),
+    ("Good", 0.08),
+    ("Solid", 0.15),
+    ("Nice", 0.25),
+    ("Great", 0.4),
+    ("Amazing", 0.5),
+    ("Genius", 0.7),
+    ("Queen Bee", 1.0),
+];
+
+/// The highest rank reached with `score` of `max_score` points
+pub fn rank(score: usize, max_score: usize) -> &'static str {
+    let share = if max_score == 0 { 0.0 } else { score as f64 / max_score as f64 };
+    RANKS
+        .iter()
+        .rev()
+        .find(|(_, needed)| share >= *needed)
+        .map_or(RANKS[0].0, |(name, _)| name)
+}
+
+/// One player's progress through one puzzle
+pub struct GameState {
+    wordlist: Wordlist,
+    /// Outer letters in display order
+    letters: Vec<char>,
+    found_words: Vec<String>,
+    /// When each of `found_words` was found, where that's known
+    stamps: Vec<Option<Stamp>>,
+    current_word: String,
+    revealed: bool,
+    hints: Vec<Hint>,
+}
+
+impl GameState {
+    pub fn new(wordlist: Wordlist, found_words: Vec<String>, revealed: bool) -> Self {
+        GameState {
+            letters: wordlist.outer.chars().collect(),
+            wordlist,
+            stamps: vec![None; found_words.len()],
+            found_words,
+            current_word: String::new(),
+            revealed,
+            hints: vec![],
+        }
+    }
+
+    /// Restores the hint tokens already spent on this puzzle from [`hints_json`](GameState::hints_json).
+    /// An unreadable record counts as none spent.
+    pub fn with_hints(self, json: &str) -> Self {
+        GameState { hints: serde_json::from_str(json).unwrap_or_default(), ..self }
+    }
+
+    /// Picks a puzzle back up from its saved [`progress`](GameState::progress)
+    pub fn resume(wordlist: Wordlist, progress: &str, revealed: bool) -> Self {
+        let (found_words, stamps) = timeline::parse(progress).into_iter().map(|find| (find.word, find.stamp)).unzip();
+        GameState { stamps, ..GameState::new(wordlist, found_words, revealed) }
+    }
+
+    pub fn apply(&mut self, action: Action) -> Events {
+        match action {
+            Action::Push(letter) if letter.is_alphabetic() => {
+                self.current_word.push(letter.to_ascii_lowercase())
+            }
+            Action::Push(_) => (),
+            Action::SetWord(word) => {
+                self.current_word = word
+                    .chars()
+                    .filter(char::is_ascii_alphabetic)
+                    .map(|c| c.to_ascii_lowercase())
+                    .collect();
+            }
+            Action::Backspace => {
+                self.current_word.pop();
+            }
+            Action::Shuffle => self.letters.shuffle(&mut rand::thread_rng()),
+            Action::Submit => return self.submit(),
+            Action::Reveal if !self.revealed => {
+                self.revealed = true;
+                return vec![Event::Revealed];
+            }
+            Action::Reveal => (),
+            Action::Hint(kind) => {
+                if let Some(hint) = self.hint(kind) {
+                    self.hints.push(hint.clone());
+                    return vec![Event::Hinted(hint)];
+                }
+            }
+        }
+        vec![]
+    }
+
+    fn submit(&mut self) -> Events {
+        if self.current_word.is_empty() {
+            return vec![];
+        }
+        let guess = std::mem::take(&mut self.current_word);
+        if !self.wordlist.words.contains(&guess) {
+            let rejection = self.check_letters(&guess).unwrap_or(Rejection::NotInWordlist);
+            vec![Event::Rejected(rejection)]
+        } else if self.found_words.contains(&guess) {
+            vec![Event::Rejected(Rejection::AlreadyFound)]
+        } else {
+            self.found_words.push(guess.clone());
+            self.stamps.push(None);
+            let mut events = vec![Event::Found(guess)];
+            if self.is_complete() && !self.revealed {
+                events.push(Event::Completed);
+            }
+            events
+        }
+    }
+
+    /// The hint a token of `kind` would buy, if there's a token left and an answer to spend it on
+    pub fn hint(&self, kind: HintKind) -> Option<Hint> {
+        if self.hints_left() == 0 || self.revealed {
+            return None;
+        }
+        let word = hints::pick(&self.wordlist, &self.found_words, &self.hints, kind)?;
+        Some(Hint { word: word.clone(), kind })
+    }
+
+    pub fn hints(&self) -> &[Hint] {
+        &self.hints
+    }
+
+    /// Spent hint tokens in the form they're persisted
+    pub fn hints_json(&self) -> String {
+        serde_json::to_string(&self.hints).unwrap()
+    }
+
+    pub fn hints_left(&self) -> usize {
+        hints::TOKENS.saturating_sub(self.hints.len())
+    }
+
+    /// Why `guess` can't be an answer, judging only by which letters it uses
+    pub fn check_letters(&self, guess: &str) -> Option<Rejection> {
+        self.wordlist.rule.check(&self.wordlist.to_set(), self.wordlist.center, guess)
+    }
+
+    pub fn wordlist(&self) -> &Wordlist {
+        &self.wordlist
+    }
+
+    pub fn letters(&self) -> &[char] {
+        &self.letters
+    }
+
+    pub fn center(&self) -> char {
+        self.wordlist.center
+    }
+
+    pub fn found_words(&self) -> &[String] {
+        &self.found_words
+    }
+
+    pub fn current_word(&self) -> &str {
+        &self.current_word
+    }
+
+    pub fn revealed(&self) -> bool {
+        self.revealed
+    }
+
+    /// Every answer has been found
+    pub fn is_complete(&self) -> bool {
+        !self.wordlist.words.is_empty() && self.found_words.len() == self.wordlist.words.len()
+    }
+
+    pub fn grid(&self) -> HashSet<char> {
+        self.wordlist.to_set()
+    }
+
+    /// The outside letters the word being typed adds
+    pub fn purple(&self) -> BTreeSet<char> {
+        added_letters(&self.grid(), &self.current_word)
+    }
+
+    /// How many unfound answers add each outside letter
+    pub fn coverage(&self) -> HashMap<char, usize> {
+        let grid = self.grid();
+        let mut coverage = HashMap::new();
+        for word in &self.wordlist.words {
+            if self.found_words.contains(word) {
+                continue;
+            }
+            for letter in added_letters(&grid, word) {
+                *coverage.entry(letter).or_insert(0) += 1;
+            }
+        }
+        coverage
+    }
+
+    pub fn score(&self) -> usize {
+        self.found_words.iter().map(|word| self.wordlist.points(word)).sum()
+    }
+
+    /// Points for finding every answer
+    pub fn max_score(&self) -> usize {
+        self.wordlist.words.iter().map(|word| self.wordlist.points(word)).sum()
+    }
+
+    pub fn rank(&self) -> &'static str {
+        rank(self.score(), self.max_score())
+    }
+
+    /// Adds answers found elsewhere, e.g. by teammates, keeping the word being typed; anything
+    /// already found or not an answer is skipped.
+    pub fn merge_found<'a>(&mut self, words: impl IntoIterator<Item=&'a String>) {
+        for word in words {
+            if self.wordlist.words.contains(word) && !self.found_words.contains(word) {
+                self.found_words.push(word.clone());
+                self.stamps.push(None);
+            }
+        }
+    }
+
+    /// Records the latest find as made at `now`, in milliseconds since the epoch. Front ends call
+    /// this on `Found`, since the core has no clock of its own.
+    pub fn stamp(&mut self, now: u64) {
+        let previous = self.stamps.iter().rev().skip(1).flatten().next().copied();
+        if let Some(latest @ None) = self.stamps.last_mut() {
+            *latest = Some(Stamp::after(previous, now));
+        }
+    }
+
+    /// Found words with when they were found, in discovery order
+    pub fn finds(&self) -> Vec<Find> {
+        self.found_words
+            .iter()
+            .zip(&self.stamps)
+            .map(|(word, stamp)| Find { word: word.clone(), stamp: *stamp })
+            .collect()
+    }
+
+    /// Milliseconds of play from the first find to find `i`. Unknown when either wasn't timed,
+    /// e.g. for a puzzle started before finds were.
+    fn elapsed(&self, i: usize) -> Option<u64> {
+        self.stamps.first()?.and(self.stamps[i]).map(|stamp| stamp.elapsed)
+    }
+
+    /// Milliseconds of play from the first find to the latest
+    pub fn play_time(&self) -> Option<u64> {
+        self.elapsed(self.stamps.len().checked_sub(1)?)
+    }
+
+    /// The score after each timed find, against milliseconds of play. Untimed finds still count
+    /// towards the score.
+    pub fn score_over_time(&self) -> Vec<(u64, usize)> {
+        let mut score = 0;
+        let mut points_over_time = vec![];
+        for (word, stamp) in self.found_words.iter().zip(&self.stamps) {
+            score += self.wordlist.points(word);
+            if let Some(stamp) = stamp {
+                points_over_time.push((stamp.elapsed, score));
+            }
+        }
+        points_over_time
+    }
+
+    /// Which find first reached the rank called `name`, and how many milliseconds of play that
+    /// took where it's known
+    pub fn reached(&self, name: &str) -> Option<(usize, Option<u64>)> {
+        let (_, needed) = RANKS.iter().find(|(rank, _)| *rank == name)?;
+        let max_score = self.max_score() as f64;
+        let mut score = 0;
+        for (i, word) in self.found_words.iter().enumerate() {
+            score += self.wordlist.points(word);
+            if score as f64 >= needed * max_score {
+                return Some((i, self.elapsed(i)));
+            }
+        }
+        None
+    }
+
+    /// Found words in the form they're persisted: a JSON list of [`Find`]s, in discovery order
+    pub fn progress(&self) -> String {
+        timeline::format(&self.finds())
+    }
+
+    /// The words in saved progress, in either the current or the older one-per-line format
+    pub fn parse_progress(progress: &str) -> Vec<String> {
+        timeline::parse(progress).into_iter().map(|find| find.word).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{rank, Action, Event, GameState, Rejection};
+    use crate::hints::{Hint, HintKind};
+    use crate::rule::LetterRule;
+    use crate::wordlist::sample;
+
+    fn game() -> GameState {
+        GameState::new(sample(&["lockage", "smocake"]), vec!["smocake".to_string()], false)
+    }
+
+    fn guess(game: &mut GameState, word: &str) -> Vec<Event> {
+        game.apply(Action::SetWord(word.to_string()));
+        game.apply(Action::Submit)
+    }
+
+    #[test]
+    fn accepts_new_answer() {
+        let mut game = game();
+        assert_eq!(guess(&mut game, "lockage"), vec![Event::Found("lockage".to_string()), Event::Completed]);
+        assert_eq!(game.found_words(), ["smocake", "lockage"]);
+        assert!(game.is_complete());
+        assert_eq!(game.current_word(), "");
+    }
+
+    #[test]
+    fn rejects_already_found() {
+        let mut game = game();
+        assert_eq!(guess(&mut game, "smocake"), vec![Event::Rejected(Rejection::AlreadyFound)]);
+        assert_eq!(game.found_words().len(), 1);
+    }
+
+    #[test]
+    fn rejects_too_many_new_letters() {
+        let mut game = game();
+        assert_eq!(guess(&mut game, "blockages"), vec![Event::Rejected(Rejection::TooManyNew)]);
+    }
+
+    #[test]
+    fn rejects_missing_center() {
+        let mut game = game();
+        assert_eq!(guess(&mut game, "gamekol"), vec![Event::Rejected(Rejection::MissingCenter)]);
+    }
+
+    #[test]
+    fn rejects_two_dropped_letters() {
+        let mut game = game();
+        assert_eq!(
+            guess(&mut game, "came"),
+            vec![Event::Rejected(Rejection::Dropped(vec!['g', 'k', 'o']))]
+        );
+    }
+
+    #[test]
+    fn rejects_well_formed_unknown_word() {
+        let mut game = game();
+        assert_eq!(guess(&mut game, "cockage"), vec![Event::Rejected(Rejection::NotInWordlist)]);
+        assert_eq!(game.current_word(), "");
+    }
+
+    #[test]
+    fn empty_submit_does_nothing() {
+        let mut game = game();
+        assert_eq!(game.apply(Action::Submit), vec![]);
+    }
+
+    #[test]
+    fn typing() {
+        let mut game = game();
+        for letter in "Lo1ck".chars() {
+            game.apply(Action::Push(letter));
+        }
+        assert_eq!(game.current_word(), "lock");
+        assert_eq!(game.purple().into_iter().collect::<String>(), "l");
+        game.apply(Action::Backspace);
+        assert_eq!(game.current_word(), "loc");
+        game.apply(Action::SetWord("Lock age".to_string()));
+        assert_eq!(game.current_word(), "lockage");
+    }
+
+    #[test]
+    fn shuffle_keeps_letters() {
+        let mut game = game();
+        game.apply(Action::Shuffle);
+        let mut letters = game.letters().to_vec();
+        letters.sort_unstable();
+        assert_eq!(letters, vec!['a', 'e', 'g', 'k', 'm', 'o']);
+    }
+
+    #[test]
+    fn reveal_once() {
+        let mut game = game();
+        assert_eq!(game.apply(Action::Reveal), vec![Event::Revealed]);
+        assert_eq!(game.apply(Action::Reveal), vec![]);
+        assert!(game.revealed());
+        // Finishing after giving up doesn't count
+        assert_eq!(guess(&mut game, "lockage"), vec![Event::Found("lockage".to_string())]);
+    }
+
+    #[test]
+    fn hints_use_up_tokens() {
+        let mut game = game();
+        let hinted = game.apply(Action::Hint(HintKind::Start));
+        assert_eq!(hinted, vec![Event::Hinted(Hint { word: "lockage".to_string(), kind: HintKind::Start })]);
+        assert_eq!(game.apply(Action::Hint(HintKind::Start)), vec![]);
+        game.apply(Action::Hint(HintKind::Length));
+        game.apply(Action::Hint(HintKind::Letters));
+        assert_eq!(game.hints_left(), 0);
+        // There's no definition to give anyway
+        assert_eq!(game.hint(HintKind::Definition), None);
+        let restored = GameState::new(game.wordlist().clone(), vec![], false).with_hints(&game.hints_json());
+        assert_eq!(restored.hints(), game.hints());
+    }
+
+    #[test]
+    fn coverage_counts_unfound_answers() {
+        let mut game = game();
+        assert_eq!(game.coverage().get(&'l'), Some(&1));
+        guess(&mut game, "lockage");
+        assert!(game.coverage().is_empty());
+
+        let mut dictionary = crate::Dictionary::default();
+        dictionary.add_level(10, "smocked\n");
+        let squared = GameState::new(dictionary.solve_with('c', "gameok", LetterRule::EMILY_SQUARED), vec![], false);
+        assert_eq!((squared.coverage().get(&'d'), squared.coverage().get(&'s')), (Some(&1), Some(&1)));
+    }
+
+    #[test]
+    fn score_counts_found_words() {
+        let mut game = game();
+        assert_eq!(game.score(), 7);
+        guess(&mut game, "lockage");
+        assert_eq!(game.score(), 14);
+    }
+
+    #[test]
+    fn rank_follows_share_of_points() {
+        let mut game = game();
+        assert_eq!(game.rank(), "Amazing");
+        guess(&mut game, "lockage");
+        assert_eq!(game.rank(), "Queen Bee");
+        assert_eq!(rank(0, 100), "Beginner");
+        assert_eq!(rank(0, 0), "Beginner");
+    }
+
+    #[test]
+    fn classic_rules() {
+        let mut dictionary = crate::Dictionary::default();
+        dictionary.add_level(10, "cameo\ngamecock\n");
+        let mut game = GameState::new(dictionary.solve_with('c', "gameok", LetterRule::CLASSIC), vec![], false);
+        assert_eq!(guess(&mut game, "lockage"), vec![Event::Rejected(Rejection::BadLetters)]);
+        assert_eq!(guess(&mut game, "coo"), vec![Event::Rejected(Rejection::TooShort)]);
+        assert_eq!(guess(&mut game, "gamecock"), vec![Event::Found("gamecock".to_string())]);
+        // Pangrams earn a bonus
+        assert_eq!(game.score(), 15);
+        assert_eq!(game.wordlist().storage_key(), "classic-cgameok");
+    }
+
+    #[test]
+    fn merges_found_words() {
+        let mut game = game();
+        game.apply(Action::Push('l'));
+        game.merge_found(&["lockage".to_string(), "smocake".to_string(), "bloop".to_string()]);
+        assert_eq!(game.found_words(), ["smocake", "lockage"]);
+        assert_eq!(game.current_word(), "l");
+    }
+
+    #[test]
+    fn progress_round_trips() {
+        let mut game = game();
+        guess(&mut game, "lockage");
+        game.stamp(1_000);
+        assert_eq!(GameState::parse_progress(&game.progress()), game.found_words());
+        let resumed = GameState::resume(game.wordlist().clone(), &game.progress(), false);
+        assert_eq!(resumed.finds(), game.finds());
+        // Progress saved before finds were timed still loads
+        assert_eq!(GameState::parse_progress("smocake\nlockage"), ["smocake", "lockage"]);
+    }
+
+    #[test]
+    fn times_finds() {
+        let mut game = game();
+        guess(&mut game, "lockage");
+        game.stamp(10_000);
+        game.stamp(20_000);
+        // The untimed first find still counts towards the score, but there's no knowing how long
+        // the puzzle took
+        assert_eq!(game.score_over_time(), vec![(0, 14)]);
+        assert_eq!(game.play_time(), None);
+        assert_eq!(game.reached("Amazing"), Some((0, None)));
+        assert_eq!(game.reached("Queen Bee"), Some((1, None)));
+        assert_eq!(game.reached("Nonsense"), None);
+
+        let mut game = GameState::new(game.wordlist().clone(), vec![], false);
+        guess(&mut game, "smocake");
+        game.stamp(10_000);
+        guess(&mut game, "lockage");
+        game.stamp(70_000);
+        assert_eq!(game.score_over_time(), vec![(0, 7), (60_000, 14)]);
+        assert_eq!(game.play_time(), Some(60_000));
+        assert_eq!(game.reached("Queen Bee"), Some((1, Some(60_000))));
+    }
+}
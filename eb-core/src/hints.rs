@@ -0,0 +1,117 @@
+use crate::wordlist::{added_letters, dropped_letters, letter_list, Wordlist};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+
+/// Hint tokens each puzzle comes with
+pub const TOKENS: usize = 3;
+
+/// What a hint token gives away about an unfound answer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HintKind {
+    Start,
+    Length,
+    Letters,
+    Definition,
+}
+
+impl HintKind {
+    pub const ALL: [HintKind; 4] = [HintKind::Start, HintKind::Length, HintKind::Letters, HintKind::Definition];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HintKind::Start => "First two letters",
+            HintKind::Length => "Length",
+            HintKind::Letters => "Dropped and added letters",
+            HintKind::Definition => "Definition",
+        }
+    }
+}
+
+/// A spent hint token
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Hint {
+    pub word: String,
+    pub kind: HintKind,
+}
+
+impl Hint {
+    /// What the hint shows, without giving away more than its kind promises
+    pub fn text(&self, wordlist: &Wordlist) -> String {
+        match self.kind {
+            HintKind::Start => format!("Starts with {}", self.word.chars().take(2).collect::<String>().to_uppercase()),
+            HintKind::Length => format!("{} letters", self.word.chars().count()),
+            HintKind::Letters => {
+                let grid = wordlist.to_set();
+                let (dropped, added) = (dropped_letters(&grid, &self.word), added_letters(&grid, &self.word));
+                let dropped = if dropped.is_empty() {
+                    "Uses every hive letter".to_string()
+                } else {
+                    format!("Leaves out {}", letter_list(&dropped))
+                };
+                let added = if added.is_empty() { "nothing".to_string() } else { letter_list(&added) };
+                format!("{} and adds {}", dropped, added)
+            }
+            HintKind::Definition => wordlist.definition(&self.word).unwrap_or_default().to_string(),
+        }
+    }
+}
+
+/// The answer a `kind` hint would be about: one already hinted at keeps getting hints until
+/// it's found, and otherwise the least obscure unfound answer is next. `None` when no unfound
+/// answer has anything left of that kind to give.
+pub fn pick<'a>(wordlist: &'a Wordlist, found_words: &[String], used: &[Hint], kind: HintKind) -> Option<&'a String> {
+    let hints_about = |word: &String| used.iter().filter(|hint| hint.word == *word).count();
+    wordlist
+        .words
+        .iter()
+        .filter(|word| !found_words.contains(word))
+        .filter(|word| !used.iter().any(|hint| hint.word == **word && hint.kind == kind))
+        .filter(|word| kind != HintKind::Definition || wordlist.definition(word).is_some())
+        .min_by_key(|word| {
+            let obscurity = wordlist.metadata.get(*word).map_or(usize::MAX, |info| info.obscurity);
+            (Reverse(hints_about(word)), obscurity)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::hints::{pick, Hint, HintKind};
+    use crate::wordlist::{sample, WordInfo};
+    use crate::Wordlist;
+
+    fn wordlist() -> Wordlist {
+        let mut wordlist = sample(&["lockage", "smocake", "gamecock"]);
+        let info = [("lockage", 50, None), ("smocake", 35, None), ("gamecock", 40, Some("A rooster bred for fighting"))];
+        for (word, obscurity, definition) in info {
+            let definition = definition.map(str::to_string);
+            wordlist.metadata.insert(word.to_string(), WordInfo { obscurity, definition, pangram: false });
+        }
+        wordlist
+    }
+
+    #[test]
+    fn hints_keep_to_one_answer() {
+        let wordlist = wordlist();
+        let first = pick(&wordlist, &[], &[], HintKind::Start).unwrap();
+        assert_eq!(first, "smocake");
+        let used = vec![Hint { word: first.clone(), kind: HintKind::Start }];
+        assert_eq!(pick(&wordlist, &[], &used, HintKind::Length).unwrap(), "smocake");
+        assert_eq!(pick(&wordlist, &[], &used, HintKind::Start).unwrap(), "gamecock");
+        // Only some answers come with a definition
+        assert_eq!(pick(&wordlist, &[], &used, HintKind::Definition).unwrap(), "gamecock");
+        assert_eq!(pick(&wordlist, &["gamecock".to_string()], &used, HintKind::Definition), None);
+    }
+
+    #[test]
+    fn hint_text() {
+        let wordlist = wordlist();
+        let text = |word: &str, kind| Hint { word: word.to_string(), kind }.text(&wordlist);
+        assert_eq!(text("smocake", HintKind::Start), "Starts with SM");
+        assert_eq!(text("smocake", HintKind::Length), "7 letters");
+        assert_eq!(text("smocake", HintKind::Letters), "Leaves out G and adds S");
+        assert_eq!(text("gamecock", HintKind::Letters), "Uses every hive letter and adds nothing");
+        assert_eq!(text("smocked", HintKind::Letters), "Leaves out A and G and adds D and S");
+        assert_eq!(text("gamecock", HintKind::Definition), "A rooster bred for fighting");
+    }
+}
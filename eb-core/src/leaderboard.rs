@@ -0,0 +1,146 @@
+//! What players send to, and get back from, `eb-leaderboard`.
+
+use crate::wordlist::Wordlist;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Handed out once per player; the key signs every summary they submit, so nobody else can
+/// post scores under their name.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Registration {
+    pub player: String,
+    pub key: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct RegisterRequest {
+    pub name: String,
+}
+
+/// A player's progress on one day's puzzle. The words are sent along so the server can check
+/// the totals against the puzzle itself.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Summary {
+    pub player: String,
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub words: Vec<String>,
+    pub word_count: usize,
+    pub score: usize,
+}
+
+impl Summary {
+    /// `words` found in `wordlist`, scored the way the game scores them
+    pub fn new(player: &str, date: &str, wordlist: &Wordlist, words: &[String]) -> Summary {
+        Summary {
+            player: player.to_string(),
+            date: date.to_string(),
+            words: words.to_vec(),
+            word_count: words.len(),
+            score: words.iter().map(|word| wordlist.points(word)).sum(),
+        }
+    }
+
+    /// The bytes that get signed: the summary's JSON, whose field order is fixed
+    fn signed_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap()
+    }
+
+    pub fn sign(self, key: &str) -> SignedSummary {
+        let signature = hex::encode(mac(key, &self.signed_bytes()).finalize().into_bytes());
+        SignedSummary { summary: self, signature }
+    }
+}
+
+fn mac(key: &str, message: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key.as_bytes()).expect("HMAC takes keys of any length");
+    mac.update(message);
+    mac
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SignedSummary {
+    pub summary: Summary,
+    /// Hex HMAC-SHA256 of the summary under the player's key
+    pub signature: String,
+}
+
+impl SignedSummary {
+    pub fn verify(&self, key: &str) -> bool {
+        match hex::decode(&self.signature) {
+            Ok(signature) => mac(key, &self.summary.signed_bytes()).verify_slice(&signature).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+/// One row of a table
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Standing {
+    pub name: String,
+    pub points: usize,
+    pub words: usize,
+    /// Puzzles the totals cover
+    pub days: usize,
+}
+
+/// Best first
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Tables {
+    pub daily: Vec<Standing>,
+    pub weekly: Vec<Standing>,
+    pub all_time: Vec<Standing>,
+}
+
+/// Days since 1970-01-01 for a `YYYY-MM-DD` date, so date ranges are plain integer ranges
+pub fn day_number(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // Howard Hinnant's days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::leaderboard::{day_number, Summary};
+    use crate::wordlist::{sample, WordInfo};
+
+    #[test]
+    fn signatures_cover_the_summary() {
+        let signed = Summary::new("p1", "2022-03-07", &sample(&["lockage"]), &["lockage".to_string()]).sign("secret");
+        assert_eq!(signed.summary.score, 7);
+        assert!(signed.verify("secret"));
+        assert!(!signed.verify("other"));
+        let mut forged = signed.clone();
+        forged.summary.score = 70;
+        assert!(!forged.verify("secret"));
+    }
+
+    #[test]
+    fn pangrams_score_their_bonus() {
+        let mut wordlist = sample(&["gamecock"]);
+        wordlist.metadata.insert("gamecock".to_string(), WordInfo { obscurity: 10, definition: None, pangram: true });
+        assert_eq!(Summary::new("p1", "2022-03-07", &wordlist, &["gamecock".to_string()]).score, 15);
+    }
+
+    #[test]
+    fn day_numbers() {
+        assert_eq!(day_number("1970-01-01"), Some(0));
+        assert_eq!(day_number("2022-03-01").unwrap() - day_number("2022-02-28").unwrap(), 1);
+        assert_eq!(day_number("2024-03-01").unwrap() - day_number("2024-02-28").unwrap(), 2);
+        assert_eq!(day_number("2022-13-01"), None);
+    }
+}
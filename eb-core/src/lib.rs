@@ -0,0 +1,26 @@
+//! Puzzle data and game rules shared by every Emily's Bee front end.
+//!
+//! Nothing in here touches the browser, so the same state machine drives the web app and can be
+//! unit tested natively.
+
+mod completion;
+mod definitions;
+mod dictionary;
+pub mod game;
+mod hints;
+pub mod leaderboard;
+pub mod room;
+mod rule;
+mod stats;
+mod timeline;
+mod wordlist;
+
+pub use completion::Completion;
+pub use definitions::Definitions;
+pub use dictionary::{is_emily_word_for, mean_obscurity, Difficulty, Dictionary, HIVE_SIZE};
+pub use game::{rank, Action, Event, Events, GameState, Rejection, RANKS};
+pub use hints::{Hint, HintKind, TOKENS};
+pub use rule::{LetterRule, Rule};
+pub use stats::Stats;
+pub use timeline::{Find, Stamp, MAX_PAUSE_MS};
+pub use wordlist::{added_letters, dropped_letters, letter_list, points, WordInfo, Wordlist, PANGRAM_BONUS};
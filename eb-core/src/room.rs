@@ -0,0 +1,112 @@
+//! Messages exchanged with `eb-server` over a WebSocket, one JSON object per text frame.
+
+use crate::wordlist::Wordlist;
+use serde::{Deserialize, Serialize};
+
+/// Letters in a room code, e.g. `QBXT`
+pub const ROOM_CODE_LEN: usize = 4;
+
+/// Room codes are typed by hand, so case and stray spaces don't matter
+pub fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Whether players compete or pool their words
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoomKind {
+    /// Everyone plays their own hive against the clock; only counts are shared
+    #[default]
+    Race,
+    /// Everyone adds to one found list, with no time limit; the room is kept across restarts
+    Team,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Opens a room on the server's current puzzle; `seconds` is the length of a race
+    Create {
+        name: String,
+        seconds: u64,
+        #[serde(default)]
+        kind: RoomKind,
+    },
+    Join { code: String, name: String },
+    /// Starts the countdown; only the player who created the room may
+    Start,
+    Submit { word: String },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// Sent once to a player entering a room; `player` indexes [`RoomState::players`]
+    Joined {
+        code: String,
+        player: usize,
+        wordlist: Wordlist,
+        #[serde(default)]
+        kind: RoomKind,
+    },
+    Room(RoomState),
+    Error { message: String },
+}
+
+/// What every player in a room sees. Races only share counts and points; team rooms also
+/// share the words, with who found each.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct RoomState {
+    pub players: Vec<PlayerScore>,
+    pub phase: Phase,
+    #[serde(default)]
+    pub found: Vec<FoundWord>,
+}
+
+/// A team room's answer and the index of the player who found it
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct FoundWord {
+    pub word: String,
+    pub player: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PlayerScore {
+    pub name: String,
+    pub words: usize,
+    pub points: usize,
+    pub connected: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "phase", rename_all = "snake_case")]
+pub enum Phase {
+    Waiting,
+    Running { remaining: u64 },
+    Finished,
+    /// Team rooms take words for as long as they exist
+    Open,
+}
+
+impl Phase {
+    pub fn accepts_words(&self) -> bool {
+        matches!(self, Phase::Running { .. } | Phase::Open)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::room::{normalize_code, ClientMessage, Phase, RoomState};
+
+    #[test]
+    fn messages_are_tagged_json() {
+        let submit = ClientMessage::Submit { word: "lockage".into() };
+        assert_eq!(serde_json::to_string(&submit).unwrap(), r#"{"type":"submit","word":"lockage"}"#);
+        let state: RoomState = serde_json::from_str(r#"{"players":[],"phase":{"phase":"running","remaining":30}}"#).unwrap();
+        assert_eq!(state.phase, Phase::Running { remaining: 30 });
+        assert_eq!(normalize_code(" qb xt"), "QBXT");
+    }
+}
@@ -0,0 +1,153 @@
+use crate::dictionary::HIVE_SIZE;
+use crate::game::Rejection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// What makes a word an answer for a hive
+pub trait Rule {
+    /// Whether `word` is an answer for the hive, as far as its letters go
+    fn accepts(&self, hive: &HashSet<char>, center: char, word: &str) -> bool;
+
+    /// Why `word` can't be an answer, when that's worth telling the player. Words that break
+    /// the rule more quietly, such as by leaving out too few letters, pass here but not
+    /// [`accepts`](Rule::accepts).
+    fn check(&self, hive: &HashSet<char>, center: char, word: &str) -> Option<Rejection>;
+}
+
+/// A rule made of how many letters answers drop and add, and a few limits besides. Puzzles
+/// record theirs, so they're played and generated the same way everywhere.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LetterRule {
+    /// Hive letters every answer leaves out; `None` for any number
+    pub dropped: Option<usize>,
+    /// Letters from outside the hive every answer brings in
+    pub added: usize,
+    pub center_required: bool,
+    pub min_length: usize,
+    /// Whether an answer may use a letter more than once
+    pub repeats: bool,
+}
+
+impl Default for LetterRule {
+    fn default() -> LetterRule {
+        LetterRule::EMILY
+    }
+}
+
+impl LetterRule {
+    /// Drop one hive letter and add one new one
+    pub const EMILY: LetterRule = LetterRule { dropped: Some(1), added: 1, center_required: true, min_length: 4, repeats: true };
+    pub const EMILY_SQUARED: LetterRule = LetterRule { dropped: Some(2), added: 2, ..LetterRule::EMILY };
+    /// The regular Spelling Bee: hive letters only, at least four of them
+    pub const CLASSIC: LetterRule = LetterRule { dropped: None, added: 0, ..LetterRule::EMILY };
+
+    pub const PRESETS: [LetterRule; 3] = [LetterRule::EMILY, LetterRule::EMILY_SQUARED, LetterRule::CLASSIC];
+
+    /// The preset's name, or one spelling out every setting
+    pub fn name(&self) -> String {
+        match *self {
+            LetterRule::EMILY => "emily".to_string(),
+            LetterRule::EMILY_SQUARED => "emily-squared".to_string(),
+            LetterRule::CLASSIC => "classic".to_string(),
+            LetterRule { dropped, added, center_required, min_length, repeats } => format!(
+                "drop-{}-add-{}{}-min-{}{}",
+                dropped.map_or("any".to_string(), |dropped| dropped.to_string()),
+                added,
+                if center_required { "" } else { "-any-center" },
+                min_length,
+                if repeats { "" } else { "-no-repeats" }
+            ),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match *self {
+            LetterRule::EMILY => "Emily’s Bee".to_string(),
+            LetterRule::EMILY_SQUARED => "Emily Squared".to_string(),
+            LetterRule::CLASSIC => "Classic".to_string(),
+            LetterRule { dropped: Some(dropped), added, .. } => format!("Drop {}, add {}", dropped, added),
+            LetterRule { added, .. } => format!("Add {}", added),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<LetterRule> {
+        LetterRule::PRESETS.iter().copied().find(|rule| rule.name() == name)
+    }
+
+    pub fn is_emily(&self) -> bool {
+        *self == LetterRule::EMILY
+    }
+
+    /// How many distinct letters every answer has, when the rule fixes it
+    pub fn distinct_letters(&self) -> Option<usize> {
+        Some(HIVE_SIZE - self.dropped? + self.added)
+    }
+}
+
+impl Rule for LetterRule {
+    fn accepts(&self, hive: &HashSet<char>, center: char, word: &str) -> bool {
+        let letters = word.chars().collect::<HashSet<_>>();
+        self.check(hive, center, word).is_none()
+            && letters.difference(hive).count() == self.added
+            && self.dropped.is_none_or(|dropped| hive.difference(&letters).count() == dropped)
+    }
+
+    fn check(&self, hive: &HashSet<char>, center: char, word: &str) -> Option<Rejection> {
+        let letters = word.chars().collect::<HashSet<_>>();
+        let length = word.chars().count();
+        if letters.difference(hive).count() > self.added {
+            Some(if self.added == 0 { Rejection::BadLetters } else { Rejection::TooManyNew })
+        } else if length < self.min_length {
+            Some(Rejection::TooShort)
+        } else if self.center_required && !letters.contains(&center) {
+            Some(Rejection::MissingCenter)
+        } else if !self.repeats && letters.len() < length {
+            Some(Rejection::RepeatedLetter)
+        } else if self.dropped.is_some_and(|dropped| hive.difference(&letters).count() > dropped) {
+            let mut missing = hive.difference(&letters).copied().collect::<Vec<_>>();
+            missing.sort_unstable();
+            Some(Rejection::Dropped(missing))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::Rejection;
+    use crate::rule::{LetterRule, Rule};
+    use std::collections::HashSet;
+
+    fn hive() -> HashSet<char> {
+        "cgameok".chars().collect()
+    }
+
+    #[test]
+    fn presets() {
+        let hive = hive();
+        assert!(LetterRule::EMILY.accepts(&hive, 'c', "lockage"));
+        assert!(!LetterRule::EMILY.accepts(&hive, 'c', "gamecock"));
+        assert!(LetterRule::CLASSIC.accepts(&hive, 'c', "gamecock"));
+        assert!(LetterRule::CLASSIC.accepts(&hive, 'c', "cameo"));
+        assert_eq!(LetterRule::CLASSIC.check(&hive, 'c', "lockage"), Some(Rejection::BadLetters));
+        // Leaves out g and a, adds s and d
+        assert!(LetterRule::EMILY_SQUARED.accepts(&hive, 'c', "smocked"));
+        assert!(!LetterRule::EMILY_SQUARED.accepts(&hive, 'c', "lockage"));
+    }
+
+    #[test]
+    fn custom_rules() {
+        let hive = hive();
+        let rule = LetterRule { repeats: false, center_required: false, ..LetterRule::CLASSIC };
+        assert_eq!(rule.check(&hive, 'c', "gamecock"), Some(Rejection::RepeatedLetter));
+        assert!(rule.accepts(&hive, 'c', "make"));
+        assert_eq!(rule.name(), "drop-any-add-0-any-center-min-4-no-repeats");
+        assert_eq!(LetterRule::from_name("emily-squared"), Some(LetterRule::EMILY_SQUARED));
+        let json = serde_json::to_string(&rule).unwrap();
+        assert_eq!(serde_json::from_str::<LetterRule>(&json).unwrap(), rule);
+        // Settings left out of a record are Emily's
+        assert_eq!(serde_json::from_str::<LetterRule>(r#"{"dropped":2,"added":2}"#).unwrap(), LetterRule::EMILY_SQUARED);
+    }
+}
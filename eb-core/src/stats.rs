@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+/// Running totals across puzzles. Daily and practice play are kept in separate records.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Stats {
+    /// Puzzles with at least one word found
+    pub played: usize,
+    pub words: usize,
+    pub points: usize,
+    /// Puzzles with every answer found
+    #[serde(default)]
+    pub completed: usize,
+    #[serde(default)]
+    pub fastest_seconds: Option<u64>,
+    /// Puzzles where Genius was reached with timed finds, and the play it took across them all
+    #[serde(default)]
+    pub genius: usize,
+    #[serde(default)]
+    pub genius_ms: u64,
+}
+
+impl Stats {
+    /// Counts a newly found word; `first` marks the first find in its puzzle
+    pub fn record_find(&mut self, points: usize, first: bool) {
+        if first {
+            self.played += 1;
+        }
+        self.words += 1;
+        self.points += points;
+    }
+
+    /// Counts a finished puzzle, and how long it took when that's known
+    pub fn record_completion(&mut self, seconds: Option<u64>) {
+        self.completed += 1;
+        if let Some(seconds) = seconds {
+            self.fastest_seconds = Some(self.fastest_seconds.map_or(seconds, |fastest| fastest.min(seconds)));
+        }
+    }
+
+    /// Counts reaching Genius after `ms` of play
+    pub fn record_genius(&mut self, ms: u64) {
+        self.genius += 1;
+        self.genius_ms += ms;
+    }
+
+    /// Milliseconds of play it takes to reach Genius, on average
+    pub fn average_to_genius(&self) -> Option<u64> {
+        (self.genius > 0).then(|| self.genius_ms / self.genius as u64)
+    }
+
+    /// Unreadable or missing records start over from zero
+    pub fn parse(json: Option<&str>) -> Stats {
+        json.and_then(|json| serde_json::from_str(json).ok()).unwrap_or_default()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stats::Stats;
+
+    #[test]
+    fn counts_puzzles_once() {
+        let mut stats = Stats::parse(Some("not json"));
+        stats.record_find(7, true);
+        stats.record_find(1, false);
+        let stats = Stats::parse(Some(&stats.to_json()));
+        assert_eq!(stats, Stats { played: 1, words: 2, points: 8, ..Stats::default() });
+    }
+
+    #[test]
+    fn keeps_the_fastest_completion() {
+        let mut stats = Stats::parse(Some(r#"{"played":3,"words":40,"points":200}"#));
+        stats.record_completion(Some(900));
+        stats.record_completion(None);
+        stats.record_completion(Some(1200));
+        assert_eq!((stats.completed, stats.fastest_seconds), (3, Some(900)));
+    }
+
+    #[test]
+    fn averages_time_to_genius() {
+        let mut stats = Stats::default();
+        assert_eq!(stats.average_to_genius(), None);
+        stats.record_genius(600_000);
+        stats.record_genius(900_000);
+        assert_eq!(stats.average_to_genius(), Some(750_000));
+    }
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// Gaps between finds longer than this count as this long, so a puzzle left open overnight
+/// doesn't look like a night's play
+pub const MAX_PAUSE_MS: u64 = 5 * 60 * 1000;
+
+/// When a word was found
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Stamp {
+    /// Milliseconds since the epoch
+    pub at: u64,
+    /// Milliseconds of play since the puzzle's first find
+    pub elapsed: u64,
+}
+
+impl Stamp {
+    /// Stamps a find at `now`, following the last one stamped, if any
+    pub fn after(previous: Option<Stamp>, now: u64) -> Stamp {
+        let elapsed = match previous {
+            Some(previous) => previous.elapsed + now.saturating_sub(previous.at).min(MAX_PAUSE_MS),
+            None => 0,
+        };
+        Stamp { at: now, elapsed }
+    }
+}
+
+/// A found word as it's saved
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Find {
+    pub word: String,
+    /// Missing for words found before finds were timed, and for words merged in from teammates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stamp: Option<Stamp>,
+}
+
+/// Reads saved progress: a JSON list of finds, or the older format of one word per line
+pub fn parse(progress: &str) -> Vec<Find> {
+    if let Ok(finds) = serde_json::from_str(progress) {
+        return finds;
+    }
+    progress
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| Find { word: line.to_owned(), stamp: None })
+        .collect()
+}
+
+pub fn format(finds: &[Find]) -> String {
+    serde_json::to_string(finds).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::timeline::{parse, Stamp, MAX_PAUSE_MS};
+
+    #[test]
+    fn reads_the_old_format() {
+        let finds = parse("lockage\nsmocake");
+        assert_eq!(finds.len(), 2);
+        assert_eq!((finds[1].word.as_str(), finds[1].stamp), ("smocake", None));
+        assert!(parse("").is_empty());
+    }
+
+    #[test]
+    fn long_pauses_are_capped() {
+        let first = Stamp::after(None, 1_000_000);
+        let second = Stamp::after(Some(first), 1_030_000);
+        let third = Stamp::after(Some(second), 1_030_000 + 8 * 60 * 60 * 1000);
+        assert_eq!((first.elapsed, second.elapsed), (0, 30_000));
+        assert_eq!(third.elapsed, 30_000 + MAX_PAUSE_MS);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::rule::LetterRule;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// A day's puzzle as written by `eb-words`
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Wordlist {
+    pub center: char,
+    pub outer: String,
+    pub words: Vec<String>,
+    /// `YYYY-MM-DD`; older puzzle files don't record it
+    #[serde(default)]
+    pub date: Option<String>,
+    #[serde(default)]
+    pub metadata: HashMap<String, WordInfo>,
+    /// What counts as an answer; puzzles written before there was a choice are Emily's
+    #[serde(default, skip_serializing_if = "LetterRule::is_emily")]
+    pub rule: LetterRule,
+    /// The same hive under the regular Spelling Bee rules, when `eb-words` solved it that way too
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classic: Option<Box<Wordlist>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct WordInfo {
+    pub obscurity: usize,
+    /// Shown in the word lists and by definition hints
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+    /// Uses every hive letter, which the regular rules give a bonus for
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pangram: bool,
+}
+
+impl Wordlist {
+    pub fn to_set(&self) -> HashSet<char> {
+        self.outer.chars().chain(Some(self.center)).collect()
+    }
+
+    /// The short definition attached to an answer by `eb-words`, if it had one
+    pub fn definition(&self, word: &str) -> Option<&str> {
+        self.metadata.get(word)?.definition.as_deref()
+    }
+
+    /// What an answer is worth in this puzzle: [`points`], plus a bonus for pangrams
+    pub fn points(&self, word: &str) -> usize {
+        let pangram = self.metadata.get(word).is_some_and(|info| info.pangram);
+        points(word) + if pangram { PANGRAM_BONUS } else { 0 }
+    }
+
+    /// Progress is keyed by the letters as published, so shuffling the hive doesn't lose it.
+    /// Puzzles under other rules are kept apart from the Emily one on the same hive.
+    pub fn storage_key(&self) -> String {
+        let mut key = if self.rule.is_emily() { String::new() } else { format!("{}-", self.rule.name()) };
+        key.push(self.center);
+        key.push_str(&self.outer);
+        key
+    }
+
+    /// Set once the player gives up, so the answers stay visible on later visits
+    pub fn revealed_key(&self) -> String {
+        format!("{}-revealed", self.storage_key())
+    }
+
+    /// Hint tokens spent on this puzzle
+    pub fn hints_key(&self) -> String {
+        format!("{}-hints", self.storage_key())
+    }
+
+    /// The `Completion` recorded once every answer is found
+    pub fn completed_key(&self) -> String {
+        format!("{}-completed", self.storage_key())
+    }
+
+    /// Best score in a blitz round of `seconds` on this puzzle
+    pub fn blitz_key(&self, seconds: u64) -> String {
+        format!("{}-blitz-{}", self.storage_key(), seconds)
+    }
+}
+
+/// Extra points for a classic pangram
+pub const PANGRAM_BONUS: usize = 7;
+
+/// What an answer is worth: a flat point for four-letter words, one per letter for longer ones
+pub fn points(word: &str) -> usize {
+    match word.chars().count() {
+        0..=4 => 1,
+        len => len,
+    }
+}
+
+/// The hive letters `word` leaves out
+pub fn dropped_letters(grid: &HashSet<char>, word: &str) -> BTreeSet<char> {
+    grid.iter().copied().filter(|letter| !word.contains(*letter)).collect()
+}
+
+/// The letters from outside the hive that `word` uses
+pub fn added_letters(grid: &HashSet<char>, word: &str) -> BTreeSet<char> {
+    word.chars().filter(|letter| !grid.contains(letter)).collect()
+}
+
+/// `letters` in capitals, joined up the way they'd be read out: "A", "A and G", "A, G and S"
+pub fn letter_list(letters: &BTreeSet<char>) -> String {
+    let mut letters = letters.iter().map(|letter| letter.to_ascii_uppercase().to_string()).collect::<Vec<_>>();
+    match letters.pop() {
+        Some(last) if letters.is_empty() => last,
+        Some(last) => format!("{} and {}", letters.join(", "), last),
+        None => String::new(),
+    }
+}
+
+/// The hive the tests play on, center `c` and outer letters `gameok`, with `words` as answers
+#[cfg(test)]
+pub(crate) fn sample(words: &[&str]) -> Wordlist {
+    Wordlist {
+        center: 'c',
+        outer: "gameok".to_string(),
+        words: words.iter().map(|word| word.to_string()).collect(),
+        date: None,
+        metadata: Default::default(),
+        rule: Default::default(),
+        classic: None,
+    }
+}
@@ -0,0 +1,201 @@
+use eb_core::leaderboard::{day_number, Registration, SignedSummary, Standing, Tables};
+use eb_core::Wordlist;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Days counted by the weekly table, ending on the requested day
+const WEEK: i64 = 7;
+
+const MAX_NAME_LEN: usize = 20;
+
+#[derive(Clone, Deserialize, Serialize)]
+struct Player {
+    id: String,
+    name: String,
+    key: String,
+}
+
+/// A player's best verified result for one day
+#[derive(Clone, Deserialize, Serialize)]
+struct Entry {
+    player: String,
+    date: String,
+    points: usize,
+    words: usize,
+}
+
+/// Players and scores, each kept as a JSON-lines file that's only ever appended to; on load
+/// later lines win, so a day's improved score simply follows its earlier one.
+pub struct Board {
+    players_path: PathBuf,
+    scores_path: PathBuf,
+    players: HashMap<String, Player>,
+    scores: HashMap<(String, String), Entry>,
+}
+
+fn read_lines<T: for<'de> Deserialize<'de>>(path: &Path) -> io::Result<Vec<T>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+    let mut items = vec![];
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            items.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(items)
+}
+
+fn append_line(path: &Path, item: &impl Serialize) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(item)?)
+}
+
+fn random_hex(rng: &mut impl Rng, bytes: usize) -> String {
+    (0..bytes).map(|_| format!("{:02x}", rng.gen::<u8>())).collect()
+}
+
+impl Board {
+    pub fn open(dir: &Path) -> io::Result<Board> {
+        std::fs::create_dir_all(dir)?;
+        let players_path = dir.join("players.jsonl");
+        let scores_path = dir.join("scores.jsonl");
+        let players = read_lines::<Player>(&players_path)?
+            .into_iter()
+            .map(|player| (player.id.clone(), player))
+            .collect();
+        let scores = read_lines::<Entry>(&scores_path)?
+            .into_iter()
+            .map(|entry| ((entry.player.clone(), entry.date.clone()), entry))
+            .collect();
+        Ok(Board { players_path, scores_path, players, scores })
+    }
+
+    pub fn register(&mut self, rng: &mut impl Rng, name: &str) -> io::Result<Registration> {
+        let mut name = name.trim().chars().take(MAX_NAME_LEN).collect::<String>();
+        if name.is_empty() {
+            name = "Anonymous".to_string();
+        }
+        let player = Player { id: random_hex(rng, 8), name, key: random_hex(rng, 32) };
+        append_line(&self.players_path, &player)?;
+        let registration = Registration { player: player.id.clone(), key: player.key.clone() };
+        self.players.insert(player.id.clone(), player);
+        Ok(registration)
+    }
+
+    /// Records a summary once its signature and totals check out against `wordlist`, the
+    /// puzzle for its date. A lower score than one already recorded for the day is ignored.
+    pub fn submit(&mut self, signed: &SignedSummary, wordlist: &Wordlist) -> Result<(), String> {
+        let summary = &signed.summary;
+        let player = self.players.get(&summary.player).ok_or("Unknown player")?;
+        if !signed.verify(&player.key) {
+            return Err("Bad signature".into());
+        }
+        let answers = wordlist.words.iter().collect::<HashSet<_>>();
+        let mut seen = HashSet::new();
+        if !summary.words.iter().all(|word| answers.contains(word) && seen.insert(word)) {
+            return Err("Those words don’t match the puzzle".into());
+        }
+        let score = summary.words.iter().map(|word| wordlist.points(word)).sum::<usize>();
+        if score != summary.score || summary.words.len() != summary.word_count {
+            return Err("Totals don’t match the words".into());
+        }
+        let key = (summary.player.clone(), summary.date.clone());
+        if self.scores.get(&key).is_some_and(|entry| entry.points >= score) {
+            return Ok(());
+        }
+        let entry = Entry {
+            player: summary.player.clone(),
+            date: summary.date.clone(),
+            points: score,
+            words: summary.word_count,
+        };
+        append_line(&self.scores_path, &entry).map_err(|e| e.to_string())?;
+        self.scores.insert(key, entry);
+        Ok(())
+    }
+
+    /// Totals per player over the days `include` accepts, best first
+    fn table(&self, include: impl Fn(i64) -> bool) -> Vec<Standing> {
+        let mut totals = HashMap::<&str, Standing>::new();
+        for entry in self.scores.values() {
+            if !day_number(&entry.date).is_some_and(&include) {
+                continue;
+            }
+            let name = self.players.get(&entry.player).map_or("Anonymous", |player| &player.name);
+            let standing = totals.entry(&entry.player).or_insert_with(|| Standing {
+                name: name.to_string(),
+                points: 0,
+                words: 0,
+                days: 0,
+            });
+            standing.points += entry.points;
+            standing.words += entry.words;
+            standing.days += 1;
+        }
+        let mut table = totals.into_values().collect::<Vec<_>>();
+        table.sort_by(|a, b| b.points.cmp(&a.points).then_with(|| a.name.cmp(&b.name)));
+        table
+    }
+
+    /// Tables for `date`: that day, the week ending on it, and every day ever
+    pub fn tables(&self, date: &str) -> Option<Tables> {
+        let day = day_number(date)?;
+        Some(Tables {
+            daily: self.table(|d| d == day),
+            weekly: self.table(|d| (day - WEEK + 1..=day).contains(&d)),
+            all_time: self.table(|_| true),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use eb_core::leaderboard::Summary;
+    use eb_core::Wordlist;
+
+    fn wordlist() -> Wordlist {
+        serde_json::from_str(r#"{"center":"c","outer":"gameok","words":["lockage","smocake"]}"#).unwrap()
+    }
+
+    #[test]
+    fn verified_scores_reach_the_tables() {
+        let wordlist = wordlist();
+        let dir = std::env::temp_dir().join(format!("eb-leaderboard-{}", std::process::id()));
+        let mut board = Board::open(&dir).unwrap();
+        let mut rng = rand::thread_rng();
+        let emily = board.register(&mut rng, "Emily").unwrap();
+        let bo = board.register(&mut rng, "Bo").unwrap();
+
+        let words = vec!["lockage".to_string()];
+        board.submit(&Summary::new(&emily.player, "2022-03-07", &wordlist, &words).sign(&emily.key), &wordlist).unwrap();
+        board.submit(&Summary::new(&bo.player, "2022-02-28", &wordlist, &words).sign(&bo.key), &wordlist).unwrap();
+        assert!(board.submit(&Summary::new(&bo.player, "2022-03-07", &wordlist, &words).sign(&emily.key), &wordlist).is_err());
+        let mut inflated = Summary::new(&bo.player, "2022-03-07", &wordlist, &words);
+        inflated.score = 70;
+        assert!(board.submit(&inflated.sign(&bo.key), &wordlist).is_err());
+        let bogus = vec!["gamecock".to_string()];
+        assert!(board.submit(&Summary::new(&bo.player, "2022-03-07", &wordlist, &bogus).sign(&bo.key), &wordlist).is_err());
+
+        let all = vec!["lockage".to_string(), "smocake".to_string()];
+        board.submit(&Summary::new(&emily.player, "2022-03-07", &wordlist, &all).sign(&emily.key), &wordlist).unwrap();
+        board.submit(&Summary::new(&emily.player, "2022-03-07", &wordlist, &words).sign(&emily.key), &wordlist).unwrap();
+
+        let reopened = Board::open(&dir).unwrap();
+        let tables = reopened.tables("2022-03-07").unwrap();
+        assert_eq!(tables.daily.len(), 1);
+        assert_eq!((tables.daily[0].name.as_str(), tables.daily[0].points), ("Emily", 14));
+        assert_eq!(tables.weekly.len(), 1);
+        assert_eq!(tables.all_time.len(), 2);
+        assert_eq!(reopened.tables("2022-02-28").unwrap().weekly[0].name, "Bo");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
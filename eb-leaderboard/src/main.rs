@@ -0,0 +1,108 @@
+mod board;
+
+use board::Board;
+use eb_core::leaderboard::{day_number, RegisterRequest, SignedSummary};
+use eb_core::Wordlist;
+use serde::Serialize;
+use std::error::Error;
+use std::io::Read;
+use std::path::PathBuf;
+use structopt::StructOpt;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Bigger bodies than this are refused; a summary is a few KB at most
+const MAX_BODY: u64 = 64 * 1024;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    #[structopt(short = "p", long = "port", default_value = "9002")]
+    port: u16,
+
+    /// Where players and scores are kept
+    #[structopt(long = "data", default_value = "data", parse(from_os_str))]
+    data: PathBuf,
+
+    /// Dated puzzle files that submitted words are checked against
+    #[structopt(long = "puzzles", default_value = "../eb-web/word-lists", parse(from_os_str))]
+    puzzles: PathBuf,
+}
+
+/// The puzzle for a `YYYY-MM-DD` date; anything else is refused before it gets near a path
+fn puzzle(opt: &Opt, date: &str) -> Result<Wordlist, String> {
+    day_number(date).ok_or("Bad date")?;
+    let json = std::fs::read_to_string(opt.puzzles.join(format!("{}.json", date)))
+        .map_err(|_| format!("No puzzle for {}", date))?;
+    serde_json::from_str(&json).map_err(|e| e.to_string())
+}
+
+fn body<T: for<'de> serde::Deserialize<'de>>(request: &mut Request) -> Result<T, String> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY)
+        .read_to_string(&mut body)
+        .map_err(|e| e.to_string())?;
+    serde_json::from_str(&body).map_err(|e| format!("Unreadable request: {}", e))
+}
+
+/// The web app is served from elsewhere, so every response allows any origin
+fn respond(request: Request, status: u16, body: String) {
+    let headers = [
+        ("Access-Control-Allow-Origin", "*"),
+        ("Access-Control-Allow-Headers", "Content-Type"),
+        ("Content-Type", "application/json"),
+    ];
+    let mut response = Response::from_string(body).with_status_code(status);
+    for (name, value) in headers.iter() {
+        response.add_header(Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap());
+    }
+    if let Err(e) = request.respond(response) {
+        eprintln!("Couldn't respond: {}", e);
+    }
+}
+
+fn reply(request: Request, result: Result<impl Serialize, String>) {
+    match result {
+        Ok(value) => respond(request, 200, serde_json::to_string(&value).unwrap()),
+        Err(message) => respond(request, 400, serde_json::to_string(&message).unwrap()),
+    }
+}
+
+/// `POST /register`, `POST /scores` and `GET /leaderboard?date=YYYY-MM-DD`
+fn route(opt: &Opt, board: &mut Board, mut request: Request) {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    match (request.method(), path) {
+        (Method::Options, _) => respond(request, 204, String::new()),
+        (Method::Post, "/register") => {
+            let result = body::<RegisterRequest>(&mut request).and_then(|register| {
+                board.register(&mut rand::thread_rng(), &register.name).map_err(|e| e.to_string())
+            });
+            reply(request, result);
+        }
+        (Method::Post, "/scores") => {
+            let result = body::<SignedSummary>(&mut request).and_then(|signed| {
+                let wordlist = puzzle(opt, &signed.summary.date)?;
+                board.submit(&signed, &wordlist)
+            });
+            reply(request, result);
+        }
+        (Method::Get, "/leaderboard") => {
+            let date = query.split('&').find_map(|pair| pair.strip_prefix("date=")).unwrap_or("");
+            let result = board.tables(date).ok_or_else(|| "Bad date".to_string());
+            reply(request, result);
+        }
+        _ => respond(request, 404, serde_json::to_string("Not found").unwrap()),
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let opt = Opt::from_args();
+    let mut board = Board::open(&opt.data)?;
+    let server = Server::http(("0.0.0.0", opt.port)).map_err(|e| e.to_string())?;
+    println!("Listening on http://localhost:{}", opt.port);
+    for request in server.incoming_requests() {
+        route(&opt, &mut board, request);
+    }
+    Ok(())
+}
@@ -0,0 +1,210 @@
+mod room;
+mod store;
+
+use eb_core::room::{normalize_code, ClientMessage, Phase, ServerMessage};
+use eb_core::Wordlist;
+use room::{Lobby, Room};
+use store::RoomStore;
+use std::error::Error;
+use std::io;
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use structopt::StructOpt;
+use tungstenite::Message;
+
+/// How long a connection waits for a frame before checking for outgoing messages
+const POLL: Duration = Duration::from_millis(100);
+
+/// How long a new connection has to send its upgrade request
+const HANDSHAKE: Duration = Duration::from_secs(10);
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    #[structopt(short = "p", long = "port", default_value = "9001")]
+    port: u16,
+
+    /// Puzzle new rooms are played on; it's read again for each room, so replacing the file
+    /// each day moves new rooms on to the new puzzle
+    #[structopt(long = "puzzle", default_value = "../eb-web/word-lists/today.json", parse(from_os_str))]
+    puzzle: PathBuf,
+
+    /// Where team rooms are kept between restarts
+    #[structopt(long = "rooms", default_value = "rooms", parse(from_os_str))]
+    rooms: PathBuf,
+}
+
+fn load(puzzle: &Path) -> Result<Wordlist, Box<dyn Error>> {
+    Ok(serde_json::from_str(&std::fs::read_to_string(puzzle)?)?)
+}
+
+/// One player's socket, and the room seat it holds once it has joined
+struct Connection {
+    lobby: Arc<Mutex<Lobby>>,
+    puzzle: PathBuf,
+    outbox: Sender<ServerMessage>,
+    seat: Option<(String, usize)>,
+}
+
+impl Connection {
+    fn handle(&mut self, message: ClientMessage) -> Result<(), String> {
+        let now = Instant::now();
+        let lobby = self.lobby.clone();
+        let mut lobby = lobby.lock().unwrap();
+        match message {
+            ClientMessage::Create { .. } | ClientMessage::Join { .. } if self.seat.is_some() => {
+                Err("Already in a room".into())
+            }
+            ClientMessage::Create { name, seconds, kind } => {
+                let wordlist = load(&self.puzzle).map_err(|e| format!("Couldn’t load today’s puzzle: {}", e))?;
+                let code = lobby.create(&mut rand::thread_rng(), Room::new(kind, wordlist, seconds));
+                self.enter(&mut lobby, code, &name, now)
+            }
+            ClientMessage::Join { code, name } => self.enter(&mut lobby, normalize_code(&code), &name, now),
+            ClientMessage::Start => {
+                let (room, player) = self.seat(&mut lobby)?;
+                room.start(player, now)?;
+                room.broadcast(now);
+                Ok(())
+            }
+            ClientMessage::Submit { word } => {
+                let (room, player) = self.seat(&mut lobby)?;
+                room.submit(player, &word, now)?;
+                room.broadcast(now);
+                lobby.save(&self.seat.as_ref().unwrap().0);
+                Ok(())
+            }
+        }
+    }
+
+    fn enter(&mut self, lobby: &mut Lobby, code: String, name: &str, now: Instant) -> Result<(), String> {
+        let room = lobby.room(&code).ok_or("No room with that code")?;
+        if !matches!(room.phase(now), Phase::Waiting | Phase::Open) {
+            return Err("That race has already started".into());
+        }
+        let player = room.join(name, self.outbox.clone());
+        let (wordlist, kind) = (room.wordlist().clone(), room.kind());
+        room.send(player, ServerMessage::Joined { code: code.clone(), player, wordlist, kind });
+        room.broadcast(now);
+        lobby.save(&code);
+        self.seat = Some((code, player));
+        Ok(())
+    }
+
+    fn seat<'a>(&self, lobby: &'a mut Lobby) -> Result<(&'a mut Room, usize), String> {
+        let (code, player) = self.seat.as_ref().ok_or("Join a room first")?;
+        let room = lobby.room(code).ok_or("That room has closed")?;
+        Ok((room, *player))
+    }
+}
+
+/// Reads and writes on the one thread: once the handshake is done, reads time out every
+/// [`POLL`] so whatever the room has queued for this player goes out promptly.
+fn serve(stream: TcpStream, lobby: Arc<Mutex<Lobby>>, puzzle: PathBuf) -> Result<(), Box<dyn Error>> {
+    // A poll-length timeout would cut the handshake short whenever the request is slow to come
+    stream.set_read_timeout(Some(HANDSHAKE))?;
+    let mut socket = tungstenite::accept(stream).map_err(|e| e.to_string())?;
+    socket.get_ref().set_read_timeout(Some(POLL))?;
+    let (outbox, inbox) = mpsc::channel();
+    let mut connection = Connection { lobby, puzzle, outbox, seat: None };
+    let result = 'connection: loop {
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                let reply = match serde_json::from_str(&text) {
+                    Ok(message) => connection.handle(message),
+                    Err(e) => Err(format!("Unreadable message: {}", e)),
+                };
+                if let Err(message) = reply {
+                    let _ = connection.outbox.send(ServerMessage::Error { message });
+                }
+            }
+            Ok(Message::Close(_)) => break Ok(()),
+            Ok(_) => (),
+            Err(tungstenite::Error::Io(e))
+                if e.kind() == io::ErrorKind::WouldBlock || e.kind() == io::ErrorKind::TimedOut => {}
+            Err(e) => break Err(e.to_string()),
+        }
+        for message in inbox.try_iter() {
+            if let Err(e) = socket.send(Message::Text(serde_json::to_string(&message).unwrap())) {
+                break 'connection Err(e.to_string());
+            }
+        }
+    };
+    if let Some((code, player)) = &connection.seat {
+        let mut lobby = connection.lobby.lock().unwrap();
+        if let Some(room) = lobby.room(code) {
+            room.leave(*player);
+            room.broadcast(Instant::now());
+        }
+    }
+    Ok(result?)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let opt = Opt::from_args();
+    load(&opt.puzzle)?;
+    let lobby = Arc::new(Mutex::new(Lobby::new(RoomStore::open(opt.rooms.clone())?)));
+
+    let ticking = lobby.clone();
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_secs(1));
+        ticking.lock().unwrap().tick(Instant::now());
+    });
+
+    let listener = TcpListener::bind(("0.0.0.0", opt.port))?;
+    println!("Listening on ws://localhost:{}", opt.port);
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Couldn't accept connection: {}", e);
+                continue;
+            }
+        };
+        let lobby = lobby.clone();
+        let puzzle = opt.puzzle.clone();
+        thread::spawn(move || {
+            if let Err(e) = serve(stream, lobby, puzzle) {
+                eprintln!("Connection closed: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::room::Lobby;
+    use crate::{serve, POLL};
+    use eb_core::room::{ClientMessage, RoomKind, ServerMessage};
+    use std::net::{TcpListener, TcpStream};
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use tungstenite::Message;
+
+    #[test]
+    fn slow_handshakes_still_connect() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let lobby = Arc::new(Mutex::new(Lobby::default()));
+            let _ = serve(stream, lobby, PathBuf::from("../eb-web/word-lists/today.json"));
+        });
+        let stream = TcpStream::connect(address).unwrap();
+        // The upgrade request arrives well after the first poll would have timed out
+        thread::sleep(POLL * 3);
+        let (mut socket, _) = tungstenite::client(format!("ws://{}", address), stream).unwrap();
+        let create = ClientMessage::Create { name: "Emily".to_string(), seconds: 60, kind: RoomKind::Race };
+        socket.send(Message::Text(serde_json::to_string(&create).unwrap())).unwrap();
+        let reply = match socket.read().unwrap() {
+            Message::Text(text) => serde_json::from_str::<ServerMessage>(&text).unwrap(),
+            other => panic!("unexpected {:?}", other),
+        };
+        assert!(matches!(reply, ServerMessage::Joined { .. }), "{:?}", reply);
+    }
+}
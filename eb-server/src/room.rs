@@ -0,0 +1,364 @@
+use crate::store::{RoomStore, SavedRoom};
+use eb_core::room::{FoundWord, Phase, PlayerScore, RoomKind, RoomState, ServerMessage, ROOM_CODE_LEN};
+use eb_core::{Action, Event, GameState, Wordlist};
+use rand::Rng;
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+/// Shortest and longest races a room can be created with
+const MIN_SECONDS: u64 = 30;
+const MAX_SECONDS: u64 = 30 * 60;
+
+const MAX_NAME_LEN: usize = 20;
+
+/// One seat in a room. In a race each player's hive is checked here too, so a client can't
+/// claim words it didn't find.
+struct Player {
+    name: String,
+    game: GameState,
+    /// `None` once the player's connection has closed
+    outbox: Option<Sender<ServerMessage>>,
+}
+
+pub struct Room {
+    kind: RoomKind,
+    wordlist: Wordlist,
+    length: Duration,
+    started: Option<Instant>,
+    players: Vec<Player>,
+    /// Whether everyone has been sent the final scores
+    finish_sent: bool,
+    /// The pooled hive of a team room
+    shared: GameState,
+    found: Vec<FoundWord>,
+}
+
+impl Room {
+    pub fn new(kind: RoomKind, wordlist: Wordlist, seconds: u64) -> Room {
+        Room {
+            kind,
+            shared: GameState::new(wordlist.clone(), vec![], false),
+            wordlist,
+            length: Duration::from_secs(seconds.clamp(MIN_SECONDS, MAX_SECONDS)),
+            started: None,
+            players: vec![],
+            finish_sent: false,
+            found: vec![],
+        }
+    }
+
+    /// A team room as it was last saved, with every player disconnected
+    pub fn restore(saved: SavedRoom) -> Room {
+        let mut room = Room::new(RoomKind::Team, saved.wordlist, 0);
+        room.players = saved
+            .players
+            .into_iter()
+            .map(|name| Player { name, game: GameState::new(room.wordlist.clone(), vec![], false), outbox: None })
+            .collect();
+        room.shared.merge_found(saved.found.iter().map(|found| &found.word));
+        room.found = saved.found;
+        room
+    }
+
+    pub fn save(&self) -> SavedRoom {
+        SavedRoom {
+            wordlist: self.wordlist.clone(),
+            players: self.players.iter().map(|player| player.name.clone()).collect(),
+            found: self.found.clone(),
+        }
+    }
+
+    pub fn kind(&self) -> RoomKind {
+        self.kind
+    }
+
+    pub fn wordlist(&self) -> &Wordlist {
+        &self.wordlist
+    }
+
+    /// Seats a player, returning their index in [`RoomState::players`]. In a team room a
+    /// returning player gets their old seat back, so their words stay credited to them.
+    pub fn join(&mut self, name: &str, outbox: Sender<ServerMessage>) -> usize {
+        let mut name = name.trim().chars().take(MAX_NAME_LEN).collect::<String>();
+        if name.is_empty() {
+            name = format!("Player {}", self.players.len() + 1);
+        }
+        if self.kind == RoomKind::Team {
+            let seat = self.players.iter().position(|player| player.name == name && player.outbox.is_none());
+            if let Some(seat) = seat {
+                self.players[seat].outbox = Some(outbox);
+                return seat;
+            }
+        }
+        self.players.push(Player {
+            name,
+            game: GameState::new(self.wordlist.clone(), vec![], false),
+            outbox: Some(outbox),
+        });
+        self.players.len() - 1
+    }
+
+    /// The player keeps their seat and score, but nothing more is sent to them
+    pub fn leave(&mut self, player: usize) {
+        if let Some(player) = self.players.get_mut(player) {
+            player.outbox = None;
+        }
+    }
+
+    pub fn is_abandoned(&self) -> bool {
+        self.players.iter().all(|player| player.outbox.is_none())
+    }
+
+    pub fn phase(&self, now: Instant) -> Phase {
+        if self.kind == RoomKind::Team {
+            return Phase::Open;
+        }
+        let started = match self.started {
+            Some(started) => started,
+            None => return Phase::Waiting,
+        };
+        match self.length.checked_sub(now.saturating_duration_since(started)) {
+            Some(remaining) if !remaining.is_zero() => Phase::Running { remaining: remaining.as_secs() },
+            _ => Phase::Finished,
+        }
+    }
+
+    /// Only the player who opened the room can start it
+    pub fn start(&mut self, player: usize, now: Instant) -> Result<(), String> {
+        if player != 0 {
+            return Err("Only the room’s creator can start the race".into());
+        }
+        if self.phase(now) != Phase::Waiting {
+            return Err("The race has already started".into());
+        }
+        self.started = Some(now);
+        Ok(())
+    }
+
+    pub fn submit(&mut self, player: usize, word: &str, now: Instant) -> Result<(), String> {
+        match self.phase(now) {
+            Phase::Waiting => return Err("The race hasn’t started yet".into()),
+            Phase::Finished => return Err("The race is over".into()),
+            Phase::Running { .. } | Phase::Open => (),
+        }
+        let game = match self.kind {
+            RoomKind::Race => &mut self.players.get_mut(player).ok_or("Not in this room")?.game,
+            RoomKind::Team => &mut self.shared,
+        };
+        game.apply(Action::SetWord(word.to_string()));
+        for event in game.apply(Action::Submit) {
+            match event {
+                Event::Found(word) if self.kind == RoomKind::Team => self.found.push(FoundWord { word, player }),
+                Event::Rejected(rejection) => return Err(rejection.message()),
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn state(&self, now: Instant) -> RoomState {
+        let score = |index: usize, player: &Player| match self.kind {
+            RoomKind::Race => (player.game.found_words().len(), player.game.score()),
+            RoomKind::Team => {
+                let mine = self.found.iter().filter(|found| found.player == index);
+                mine.fold((0, 0), |(words, total), found| (words + 1, total + self.wordlist.points(&found.word)))
+            }
+        };
+        RoomState {
+            players: self
+                .players
+                .iter()
+                .enumerate()
+                .map(|(index, player)| {
+                    let (words, points) = score(index, player);
+                    PlayerScore {
+                        name: player.name.clone(),
+                        words,
+                        points,
+                        connected: player.outbox.is_some(),
+                    }
+                })
+                .collect(),
+            phase: self.phase(now),
+            found: self.found.clone(),
+        }
+    }
+
+    pub fn send(&self, player: usize, message: ServerMessage) {
+        if let Some(outbox) = self.players.get(player).and_then(|player| player.outbox.as_ref()) {
+            let _ = outbox.send(message);
+        }
+    }
+
+    pub fn broadcast(&self, now: Instant) {
+        let state = self.state(now);
+        for player in 0..self.players.len() {
+            self.send(player, ServerMessage::Room(state.clone()));
+        }
+    }
+}
+
+/// Every open room, by code. Team rooms are also written to the store, and read back from it
+/// the first time someone asks for one after a restart.
+#[derive(Default)]
+pub struct Lobby {
+    rooms: HashMap<String, Room>,
+    store: Option<RoomStore>,
+}
+
+impl Lobby {
+    pub fn new(store: RoomStore) -> Lobby {
+        Lobby {
+            rooms: HashMap::new(),
+            store: Some(store),
+        }
+    }
+
+    /// Opens a room under a fresh code
+    pub fn create(&mut self, rng: &mut impl Rng, room: Room) -> String {
+        loop {
+            let code = (0..ROOM_CODE_LEN)
+                .map(|_| rng.gen_range(b'A'..=b'Z') as char)
+                .collect::<String>();
+            let saved = self.store.as_ref().is_some_and(|store| store.contains(&code));
+            if !self.rooms.contains_key(&code) && !saved {
+                self.rooms.insert(code.clone(), room);
+                self.save(&code);
+                return code;
+            }
+        }
+    }
+
+    pub fn room(&mut self, code: &str) -> Option<&mut Room> {
+        if !self.rooms.contains_key(code) {
+            let saved = self.store.as_ref()?.load(code)?;
+            self.rooms.insert(code.to_string(), Room::restore(saved));
+        }
+        self.rooms.get_mut(code)
+    }
+
+    /// Writes a team room to the store; races aren't worth keeping
+    pub fn save(&self, code: &str) {
+        let (room, store) = match (self.rooms.get(code), &self.store) {
+            (Some(room), Some(store)) if room.kind == RoomKind::Team => (room, store),
+            _ => return,
+        };
+        if let Err(e) = store.save(code, &room.save()) {
+            eprintln!("Couldn't save room {}: {}", code, e);
+        }
+    }
+
+    /// Called about once a second: sends running rooms their countdown, sends finished ones
+    /// their final scores once, and closes rooms everyone has left.
+    pub fn tick(&mut self, now: Instant) {
+        self.rooms.retain(|_, room| !room.is_abandoned());
+        for room in self.rooms.values_mut() {
+            match room.phase(now) {
+                Phase::Waiting | Phase::Open => (),
+                Phase::Running { .. } => room.broadcast(now),
+                Phase::Finished if !room.finish_sent => {
+                    room.broadcast(now);
+                    room.finish_sent = true;
+                }
+                Phase::Finished => (),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::room::{Lobby, Room};
+    use crate::store::RoomStore;
+    use eb_core::room::{FoundWord, Phase, RoomKind, ServerMessage};
+    use eb_core::Wordlist;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    fn wordlist() -> Wordlist {
+        serde_json::from_str(r#"{"center":"c","outer":"gameok","words":["lockage","smocake"]}"#).unwrap()
+    }
+
+    #[test]
+    fn race_scores_only_while_running() {
+        let (outbox, inbox) = mpsc::channel();
+        let mut room = Room::new(RoomKind::Race, wordlist(), 60);
+        let host = room.join("Emily", outbox.clone());
+        let guest = room.join("  ", outbox);
+        let now = Instant::now();
+        assert!(room.submit(host, "lockage", now).is_err());
+        assert!(room.start(guest, now).is_err());
+        room.start(host, now).unwrap();
+        room.submit(guest, "smocake", now).unwrap();
+        assert_eq!(room.submit(guest, "smocake", now), Err("Already found".into()));
+        assert!(room.submit(host, "gamecock", now).is_err());
+
+        let state = room.state(now + Duration::from_secs(15));
+        assert_eq!(state.phase, Phase::Running { remaining: 45 });
+        assert_eq!(state.players[1].name, "Player 2");
+        assert_eq!((state.players[0].words, state.players[1].words), (0, 1));
+        assert_eq!(state.players[1].points, 7);
+        assert!(state.found.is_empty());
+
+        let later = now + Duration::from_secs(60);
+        assert_eq!(room.phase(later), Phase::Finished);
+        assert!(room.submit(host, "lockage", later).is_err());
+
+        room.broadcast(now);
+        assert!(matches!(inbox.try_recv(), Ok(ServerMessage::Room(_))));
+    }
+
+    #[test]
+    fn team_pools_and_credits_words() {
+        let (outbox, _inbox) = mpsc::channel();
+        let mut room = Room::new(RoomKind::Team, wordlist(), 0);
+        let emily = room.join("Emily", outbox.clone());
+        let bo = room.join("Bo", outbox.clone());
+        let now = Instant::now();
+        room.submit(bo, "smocake", now).unwrap();
+        assert_eq!(room.submit(emily, "smocake", now), Err("Already found".into()));
+        room.submit(emily, "lockage", now).unwrap();
+
+        let state = room.state(now);
+        assert_eq!(state.phase, Phase::Open);
+        assert_eq!(state.found[0], FoundWord { word: "smocake".into(), player: bo });
+        assert_eq!((state.players[emily].words, state.players[emily].points), (1, 7));
+
+        room.leave(bo);
+        assert_eq!(room.join("Bo", outbox), bo);
+    }
+
+    #[test]
+    fn team_rooms_survive_restarts() {
+        let dir = std::env::temp_dir().join(format!("eb-rooms-{}", std::process::id()));
+        let mut lobby = Lobby::new(RoomStore::open(dir.clone()).unwrap());
+        let (outbox, _inbox) = mpsc::channel();
+        let code = lobby.create(&mut rand::thread_rng(), Room::new(RoomKind::Team, wordlist(), 0));
+        let room = lobby.room(&code).unwrap();
+        let player = room.join("Emily", outbox.clone());
+        room.submit(player, "lockage", Instant::now()).unwrap();
+        lobby.save(&code);
+
+        let mut restarted = Lobby::new(RoomStore::open(dir.clone()).unwrap());
+        let room = restarted.room(&code).unwrap();
+        assert_eq!(room.kind(), RoomKind::Team);
+        assert_eq!(room.join("Emily", outbox), player);
+        assert_eq!(room.submit(player, "lockage", Instant::now()), Err("Already found".into()));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn abandoned_rooms_close() {
+        let mut lobby = Lobby::default();
+        let (outbox, _inbox) = mpsc::channel();
+        let mut room = Room::new(RoomKind::Race, wordlist(), 60);
+        let player = room.join("Emily", outbox);
+        let code = lobby.create(&mut rand::thread_rng(), room);
+        assert_eq!(code.len(), 4);
+        lobby.tick(Instant::now());
+        lobby.room(&code).unwrap().leave(player);
+        lobby.tick(Instant::now());
+        assert!(lobby.room(&code).is_none());
+    }
+}
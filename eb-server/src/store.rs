@@ -0,0 +1,52 @@
+use eb_core::room::FoundWord;
+use eb_core::Wordlist;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+
+/// What's kept of a team room: the puzzle, who has played and what they found
+#[derive(Deserialize, Serialize)]
+pub struct SavedRoom {
+    pub wordlist: Wordlist,
+    pub players: Vec<String>,
+    pub found: Vec<FoundWord>,
+}
+
+/// Team rooms on disk, one `<CODE>.json` file each
+pub struct RoomStore {
+    dir: PathBuf,
+}
+
+impl RoomStore {
+    pub fn open(dir: PathBuf) -> io::Result<RoomStore> {
+        std::fs::create_dir_all(&dir)?;
+        Ok(RoomStore { dir })
+    }
+
+    fn path(&self, code: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", code))
+    }
+
+    pub fn contains(&self, code: &str) -> bool {
+        self.path(code).exists()
+    }
+
+    /// `None` for unknown codes; a file that can't be read is reported and treated the same
+    pub fn load(&self, code: &str) -> Option<SavedRoom> {
+        let contents = std::fs::read_to_string(self.path(code)).ok()?;
+        match serde_json::from_str(&contents) {
+            Ok(saved) => Some(saved),
+            Err(e) => {
+                eprintln!("Couldn't read room {}: {}", code, e);
+                None
+            }
+        }
+    }
+
+    /// Written to a temporary file first so a crash mid-write can't lose the room
+    pub fn save(&self, code: &str, room: &SavedRoom) -> io::Result<()> {
+        let temporary = self.dir.join(format!("{}.json.tmp", code));
+        std::fs::write(&temporary, serde_json::to_string(room)?)?;
+        std::fs::rename(temporary, self.path(code))
+    }
+}
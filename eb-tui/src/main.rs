@@ -0,0 +1,265 @@
+mod store;
+
+use crossterm::event::{self as term, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Stylize;
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute, queue};
+use eb_core::{Action, Event, GameState, HintKind, Wordlist};
+use std::error::Error;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use store::Store;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// Puzzle to play, as `YYYY-MM-DD`; defaults to today's
+    #[structopt(short = "d", long = "date")]
+    date: Option<String>,
+
+    /// Directory holding the dated puzzle files
+    #[structopt(long = "dir", default_value = "../eb-web/word-lists", parse(from_os_str))]
+    dir: PathBuf,
+
+    /// Load the puzzle JSON from here instead of `--dir`
+    #[structopt(short = "u", long = "url")]
+    url: Option<String>,
+
+    /// Where progress is kept, using the same keys as the web app's local storage
+    #[structopt(short = "p", long = "progress", parse(from_os_str))]
+    progress: Option<PathBuf>,
+}
+
+fn load(opt: &Opt) -> Result<Wordlist, Box<dyn Error>> {
+    let json = match &opt.url {
+        Some(url) => reqwest::blocking::get(url.as_str())?.error_for_status()?.text()?,
+        None => {
+            let name = opt.date.as_deref().unwrap_or("today");
+            std::fs::read_to_string(opt.dir.join(format!("{}.json", name)))?
+        }
+    };
+    let mut wordlist: Wordlist = serde_json::from_str(&json)?;
+    if wordlist.date.is_none() {
+        wordlist.date = opt.date.clone();
+    }
+    Ok(wordlist)
+}
+
+fn progress_path(opt: &Opt) -> PathBuf {
+    match &opt.progress {
+        Some(path) => path.clone(),
+        None => std::env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_default()
+            .join(".eb-progress.json"),
+    }
+}
+
+struct App {
+    game: GameState,
+    store: Store,
+    message: Option<String>,
+    hints: bool,
+}
+
+impl App {
+    fn restore(wordlist: Wordlist, store: Store) -> App {
+        let progress = store.get_item(&wordlist.storage_key()).unwrap_or_default();
+        let revealed = store.get_item(&wordlist.revealed_key()).is_some();
+        let hints = store.get_item(&wordlist.hints_key()).unwrap_or_default();
+        App {
+            game: GameState::resume(wordlist, progress, revealed).with_hints(hints),
+            store,
+            message: None,
+            hints: false,
+        }
+    }
+
+    fn apply(&mut self, action: Action) -> io::Result<()> {
+        self.message = None;
+        for event in self.game.apply(action) {
+            match event {
+                Event::Found(word) => {
+                    self.message = Some(format!("Nice! +{}", self.game.wordlist().points(&word)));
+                    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_millis() as u64);
+                    self.game.stamp(now);
+                    self.store.set_item(&self.game.wordlist().storage_key(), &self.game.progress())?;
+                }
+                Event::Completed => self.message = Some("Queen Bee! Every word found".to_string()),
+                Event::Rejected(rejection) => self.message = Some(rejection.message()),
+                Event::Revealed => self.store.set_item(&self.game.wordlist().revealed_key(), "true")?,
+                Event::Hinted(hint) => {
+                    self.message = Some(format!("Hint: {}", hint.text(self.game.wordlist())));
+                    self.store.set_item(&self.game.wordlist().hints_key(), &self.game.hints_json())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Flat-topped hexagons: the six outer letters around the center one
+fn hive(center: char, outer: &[char]) -> Vec<String> {
+    let l = |i: usize| outer.get(i).copied().unwrap_or(' ').to_ascii_uppercase();
+    vec![
+        "     ___".to_string(),
+        format!(" ___/ {} \\___", l(0)),
+        format!("/ {} \\___/ {} \\", l(1), l(2)),
+        format!("\\___/ {} \\___/", center.to_ascii_uppercase()),
+        format!("/ {} \\___/ {} \\", l(3), l(4)),
+        format!("\\___/ {} \\___/", l(5)),
+        "    \\___/".to_string(),
+    ]
+}
+
+/// Word lists are wrapped to fit comfortably in an 80 column terminal
+fn wrap(label: &str, words: &[&str]) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = label.to_string();
+    for word in words {
+        if line.len() + word.len() > 72 {
+            lines.push(std::mem::take(&mut line));
+            line.push_str("   ");
+        }
+        line.push(' ');
+        line.push_str(word);
+    }
+    lines.push(line);
+    lines
+}
+
+fn render(app: &App) -> Vec<String> {
+    let game = &app.game;
+    let wordlist = game.wordlist();
+    let mut lines = vec![format!(
+        "Emily's Bee {}    Score {}    Words {}/{}",
+        wordlist.date.as_deref().unwrap_or(""),
+        game.score(),
+        game.found_words().len(),
+        wordlist.words.len()
+    )];
+    lines.push(String::new());
+    lines.extend(hive(game.center(), game.letters()).into_iter().map(|line| format!("   {}", line)));
+    lines.push(String::new());
+    lines.push(format!("> {}_", game.current_word().to_ascii_uppercase()));
+    lines.push(app.message.clone().unwrap_or_default());
+    lines.push(String::new());
+    let found = game.found_words().iter().map(|word| word.as_str()).collect::<Vec<_>>();
+    lines.extend(wrap("Found:", &found));
+    if game.revealed() {
+        let missed = wordlist
+            .words
+            .iter()
+            .filter(|word| !game.found_words().contains(word))
+            .map(|word| word.as_str())
+            .collect::<Vec<_>>();
+        lines.extend(wrap("Missed:", &missed));
+    }
+    if app.hints {
+        let mut coverage = game.coverage().into_iter().collect::<Vec<_>>();
+        coverage.sort_unstable();
+        let hints = coverage
+            .iter()
+            .map(|(letter, count)| format!("+{} {}", letter, count))
+            .collect::<Vec<_>>();
+        lines.push(format!("Remaining by added letter: {}", hints.join("  ")));
+    }
+    if !game.hints().is_empty() {
+        lines.push(format!("Hint tokens left: {}", game.hints_left()));
+        for hint in game.hints() {
+            let status = if game.found_words().contains(&hint.word) { " (found)" } else { "" };
+            lines.push(format!("  {}{}", hint.text(wordlist), status));
+        }
+    }
+    lines.push(String::new());
+    lines.push("Enter submit · Space shuffle · Backspace delete · Tab hints · 1-4 hint token · Ctrl-R reveal · Esc quit".to_string());
+    lines
+}
+
+fn draw(out: &mut impl Write, app: &App) -> io::Result<()> {
+    queue!(out, terminal::Clear(ClearType::All))?;
+    for (row, line) in render(app).iter().enumerate() {
+        queue!(out, cursor::MoveTo(0, row as u16))?;
+        if row == 0 {
+            write!(out, "{}", line.as_str().bold())?;
+        } else {
+            write!(out, "{}", line)?;
+        }
+    }
+    out.flush()
+}
+
+fn action_for(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Reveal),
+        KeyCode::Char(' ') => Some(Action::Shuffle),
+        KeyCode::Char(digit @ '1'..='4') => Some(Action::Hint(HintKind::ALL[digit as usize - '1' as usize])),
+        KeyCode::Char(letter) if !key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Push(letter)),
+        KeyCode::Backspace => Some(Action::Backspace),
+        KeyCode::Enter => Some(Action::Submit),
+        _ => None,
+    }
+}
+
+fn run(out: &mut impl Write, app: &mut App) -> io::Result<()> {
+    loop {
+        draw(out, app)?;
+        let key = match term::read()? {
+            term::Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        match key.code {
+            KeyCode::Esc => return Ok(()),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+            KeyCode::Tab => app.hints = !app.hints,
+            _ => {
+                if let Some(action) = action_for(key) {
+                    app.apply(action)?;
+                }
+            }
+        }
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let opt = Opt::from_args();
+    let wordlist = load(&opt)?;
+    let store = Store::open(progress_path(&opt))?;
+    let mut app = App::restore(wordlist, store);
+
+    let mut out = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let result = run(&mut out, &mut app);
+    execute!(out, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result?;
+    println!("{} words, {} points", app.game.found_words().len(), app.game.score());
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{hive, wrap};
+
+    #[test]
+    fn hive_places_every_letter() {
+        let hive = hive('c', &['g', 'a', 'm', 'e', 'o', 'k']);
+        let letters = hive
+            .concat()
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .collect::<String>();
+        assert_eq!(letters, "GAMCEOK");
+        assert_eq!(hive[3], "\\___/ C \\___/");
+    }
+
+    #[test]
+    fn wraps_long_lists() {
+        let words = vec!["partiality"; 12];
+        let lines = wrap("Found:", &words);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| line.len() <= 84));
+    }
+}
@@ -0,0 +1,31 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+
+/// Local stand-in for the browser's `localStorage`: the same keys and values the web app
+/// writes, saved together as one JSON object.
+pub struct Store {
+    path: PathBuf,
+    items: BTreeMap<String, String>,
+}
+
+impl Store {
+    /// A missing file is an empty store; it's created on the first write
+    pub fn open(path: PathBuf) -> io::Result<Store> {
+        let items = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Store { path, items })
+    }
+
+    pub fn get_item(&self, key: &str) -> Option<&str> {
+        self.items.get(key).map(|value| value.as_str())
+    }
+
+    pub fn set_item(&mut self, key: &str, value: &str) -> io::Result<()> {
+        self.items.insert(key.to_string(), value.to_string());
+        std::fs::write(&self.path, serde_json::to_string_pretty(&self.items)?)
+    }
+}
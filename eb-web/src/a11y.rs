@@ -0,0 +1,122 @@
+//! What screen readers hear and where keyboard focus goes for the hive and the on-screen
+//! keyboard.
+
+use eb_core::GameState;
+use wasm_bindgen::JsCast;
+use yew::web_sys::{Element, HtmlElement, KeyboardEvent, SvgElement};
+
+/// Id of the text that describes the whole puzzle, referenced by the hive
+pub const DESCRIPTION_ID: &str = "puzzle-description";
+
+/// Handles a key pressed on a focused hive cell, keyboard key or hive button. The arrow keys
+/// move focus to the previous or next focusable sibling, wrapping round; Enter and Space press
+/// it, and true is returned. Handled keys have their default cancelled, which also tells the
+/// window-level key handler to leave them alone.
+pub fn press_or_move(e: &KeyboardEvent) -> bool {
+    let step = match e.key().as_str() {
+        "Enter" | " " => {
+            e.prevent_default();
+            return true;
+        }
+        "ArrowRight" | "ArrowDown" => 1,
+        "ArrowLeft" | "ArrowUp" => -1,
+        _ => return false,
+    };
+    e.prevent_default();
+    if let Some(current) = e.current_target().and_then(|target| target.dyn_into::<Element>().ok()) {
+        focus_sibling(&current, step);
+    }
+    false
+}
+
+fn focus_sibling(current: &Element, step: isize) {
+    let siblings = match current.parent_element() {
+        Some(parent) => parent.children(),
+        None => return,
+    };
+    let focusable = (0..siblings.length())
+        .filter_map(|i| siblings.item(i))
+        .filter(|el| el.has_attribute("tabindex"))
+        .collect::<Vec<_>>();
+    let index = match focusable.iter().position(|el| el == current) {
+        Some(index) => index as isize,
+        None => return,
+    };
+    let next = &focusable[(index + step).rem_euclid(focusable.len() as isize) as usize];
+    // Hive cells and keys are SVG, the hive buttons HTML; both can take focus
+    if let Some(svg) = next.dyn_ref::<SvgElement>() {
+        let _ = svg.focus();
+    } else if let Some(html) = next.dyn_ref::<HtmlElement>() {
+        let _ = html.focus();
+    }
+}
+
+pub fn cell_label(letter: char, center: bool) -> String {
+    let letter = letter.to_ascii_uppercase();
+    if center {
+        format!("Center letter {}", letter)
+    } else {
+        format!("Letter {}", letter)
+    }
+}
+
+fn spelled(word: &str) -> String {
+    word.to_uppercase().chars().map(String::from).collect::<Vec<_>>().join(" ")
+}
+
+/// What the live region should say next. Screen readers only read it out when its text
+/// changes, so a repeat of the last announcement gets a zero-width space on the end.
+pub fn announcement(previous: &str, next: String) -> String {
+    if previous == next {
+        next + "\u{200B}"
+    } else {
+        next
+    }
+}
+
+/// The puzzle in a few sentences: its letters, the word being typed and how far along it is
+pub fn describe(game: &GameState) -> String {
+    let current = game.current_word();
+    let typed = if current.is_empty() {
+        "Nothing typed".to_string()
+    } else {
+        format!("Typed {}", spelled(current))
+    };
+    let outer = game.letters().iter().collect::<String>();
+    format!(
+        "Center letter {}, outside letters {}. {}. {} of {} words found, {} points, rank {}.",
+        game.center().to_ascii_uppercase(),
+        spelled(&outer),
+        typed,
+        game.found_words().len(),
+        game.wordlist().words.len(),
+        game.score(),
+        game.rank(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::a11y::{announcement, cell_label, describe};
+    use eb_core::{Action, GameState, Wordlist};
+
+    #[test]
+    fn description_reads_the_puzzle() {
+        let wordlist: Wordlist =
+            serde_json::from_str(r#"{"center":"c","outer":"gameok","words":["lockage","smocake"]}"#).unwrap();
+        let mut game = GameState::new(wordlist, vec!["lockage".to_string()], false);
+        game.apply(Action::Push('c'));
+        let description = describe(&game);
+        assert!(description.starts_with("Center letter C, outside letters "), "{}", description);
+        assert!(description.contains(". Typed C. 1 of 2 words found, 7 points, rank "), "{}", description);
+        assert_eq!(cell_label('g', false), "Letter G");
+    }
+
+    #[test]
+    fn repeated_announcements_change() {
+        let first = announcement("", "Rejected: Already found".to_string());
+        let second = announcement(&first, "Rejected: Already found".to_string());
+        assert_ne!(first, second);
+        assert_eq!(announcement(&second, first.clone()), first);
+    }
+}
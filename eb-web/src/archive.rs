@@ -0,0 +1,137 @@
+use eb_core::Wordlist;
+use yew::format::{Nothing, Text};
+use yew::services::fetch::{FetchService, FetchTask, Request, Response};
+use yew::Callback;
+
+/// Puzzle files are copied next to the app by trunk, one per `YYYY-MM-DD`
+pub fn puzzle_url(date: &str) -> String {
+    format!("word-lists/{}.json", date)
+}
+
+/// Link that opens the app on the puzzle for `date`
+pub fn archive_url(date: &str) -> String {
+    format!("?date={}", date)
+}
+
+/// The value of `name` in a `?a=1&b=2` query string
+pub fn query_param(search: &str, name: &str) -> Option<String> {
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+        .map(|value| value.to_string())
+}
+
+/// The `date` from `?date=YYYY-MM-DD`, if the page was opened on an archived puzzle
+pub fn requested_date(search: &str) -> Option<String> {
+    query_param(search, "date").filter(|date| parse(date).is_some())
+}
+
+pub fn fetch(date: &str, callback: Callback<Option<Wordlist>>) -> Option<FetchTask> {
+    let request = Request::get(puzzle_url(date)).body(Nothing).ok()?;
+    let date = date.to_string();
+    let handler = move |response: Response<Text>| {
+        let wordlist = match response.into_body() {
+            Ok(body) => serde_json::from_str::<Wordlist>(&body).ok(),
+            Err(_) => None,
+        };
+        // Older files only carry their date in the file name
+        callback.emit(wordlist.map(|mut wordlist| {
+            wordlist.date.get_or_insert_with(|| date.clone());
+            wordlist
+        }))
+    };
+    FetchService::fetch(request, handler.into()).ok()
+}
+
+fn parse(date: &str) -> Option<(i32, u32, u32)> {
+    let mut parts = date.splitn(3, '-');
+    let year = parts.next()?;
+    let month = parts.next()?;
+    let day = parts.next()?;
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+    let (year, month, day) = (year.parse().ok()?, month.parse().ok()?, day.parse().ok()?);
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some((year, month, day))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+pub fn previous_day(date: &str) -> Option<String> {
+    let (mut year, mut month, mut day) = parse(date)?;
+    if day > 1 {
+        day -= 1;
+    } else {
+        if month > 1 {
+            month -= 1;
+        } else {
+            month = 12;
+            year -= 1;
+        }
+        day = days_in_month(year, month);
+    }
+    Some(format!("{:04}-{:02}-{:02}", year, month, day))
+}
+
+/// Whether a fetched puzzle's date comes after the bundled one's. The local date a puzzle is
+/// fetched for can be behind the date `eb-words` publishes by, so the fetch may be older.
+pub fn is_newer(fetched: Option<&str>, bundled: Option<&str>) -> bool {
+    match (fetched, bundled) {
+        // `YYYY-MM-DD` sorts by date
+        (Some(fetched), Some(bundled)) => fetched > bundled,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+/// The local calendar date, for puzzles that don't record their own
+pub fn today() -> String {
+    let now = js_sys::Date::new_0();
+    format!(
+        "{:04}-{:02}-{:02}",
+        now.get_full_year(),
+        now.get_month() + 1,
+        now.get_date()
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::archive::{is_newer, previous_day, requested_date};
+
+    #[test]
+    fn previous_days() {
+        assert_eq!(previous_day("2022-03-07").as_deref(), Some("2022-03-06"));
+        assert_eq!(previous_day("2022-03-01").as_deref(), Some("2022-02-28"));
+        assert_eq!(previous_day("2020-03-01").as_deref(), Some("2020-02-29"));
+        assert_eq!(previous_day("2022-01-01").as_deref(), Some("2021-12-31"));
+        assert_eq!(previous_day("today"), None);
+    }
+
+    #[test]
+    fn query_dates() {
+        assert_eq!(requested_date("?date=2021-11-08").as_deref(), Some("2021-11-08"));
+        assert_eq!(requested_date("?x=1&date=2021-11-08").as_deref(), Some("2021-11-08"));
+        assert_eq!(requested_date("?date=../secrets"), None);
+        assert_eq!(requested_date(""), None);
+    }
+
+    #[test]
+    fn newer_puzzles() {
+        assert!(is_newer(Some("2022-03-08"), Some("2022-03-07")));
+        assert!(!is_newer(Some("2022-03-06"), Some("2022-03-07")));
+        assert!(!is_newer(Some("2022-03-07"), Some("2022-03-07")));
+        assert!(!is_newer(None, Some("2022-03-07")));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::practice::Mode;
+use eb_core::{GameState, Wordlist};
+use gloo_timers::callback::Interval;
+use yew::web_sys::Storage;
+
+/// Round lengths offered, in seconds
+pub const DURATIONS: [u64; 3] = [60, 180, 300];
+pub const DEFAULT_SECONDS: u64 = 180;
+
+/// Whole seconds left before `deadline`, both in milliseconds since the epoch
+pub fn seconds_left(deadline: f64, now: f64) -> u64 {
+    ((deadline - now).max(0.0) / 1000.0).ceil() as u64
+}
+
+/// `m:ss`
+pub fn clock(seconds: u64) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+pub fn best(storage: &Storage, wordlist: &Wordlist, seconds: u64) -> Option<usize> {
+    storage.get_item(&wordlist.blitz_key(seconds)).unwrap()?.parse().ok()
+}
+
+/// A timed round on a fresh copy of a puzzle. The game it interrupted is put aside untouched
+/// and comes back when the round is closed.
+pub struct Blitz {
+    pub seconds: u64,
+    deadline: f64,
+    pub remaining: u64,
+    /// Best score for this puzzle and length before this round
+    pub best: Option<usize>,
+    pub paused: (GameState, Mode),
+    /// Ticks every second until time runs out
+    interval: Option<Interval>,
+}
+
+impl Blitz {
+    pub fn new(seconds: u64, now: f64, best: Option<usize>, paused: (GameState, Mode), interval: Interval) -> Blitz {
+        Blitz {
+            seconds,
+            deadline: now + seconds as f64 * 1000.0,
+            remaining: seconds,
+            best,
+            paused,
+            interval: Some(interval),
+        }
+    }
+
+    pub fn running(&self) -> bool {
+        self.interval.is_some()
+    }
+
+    /// Returns true when this tick ran the clock out
+    pub fn tick(&mut self, now: f64) -> bool {
+        self.remaining = seconds_left(self.deadline, now);
+        if self.remaining == 0 && self.running() {
+            self.interval = None;
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::blitz::{clock, seconds_left};
+
+    #[test]
+    fn countdown() {
+        assert_eq!(seconds_left(10_000.0, 0.0), 10);
+        assert_eq!(seconds_left(10_000.0, 500.0), 10);
+        assert_eq!(seconds_left(10_000.0, 9_001.0), 1);
+        assert_eq!(seconds_left(10_000.0, 12_000.0), 0);
+        assert_eq!(clock(185), "3:05");
+    }
+}
@@ -0,0 +1,62 @@
+use crate::blitz;
+use eb_core::{GameState, Stats, RANKS};
+use yew::{html, Html};
+
+const WIDTH: f64 = 300.0;
+const HEIGHT: f64 = 120.0;
+
+/// The rank whose time is kept in the stats
+pub const GENIUS: &str = "Genius";
+
+/// `points` as SVG coordinates, time across and score up, with `max_score` at the top. A line
+/// is drawn from the origin so the first find has something to join.
+pub fn polyline(points: &[(u64, usize)], max_score: usize) -> String {
+    let last = points.last().map_or(0, |(elapsed, _)| *elapsed).max(1) as f64;
+    let max_score = max_score.max(1) as f64;
+    let scale = |(elapsed, score): (u64, usize)| {
+        let x = elapsed as f64 / last * WIDTH;
+        let y = HEIGHT - score as f64 / max_score * HEIGHT;
+        format!("{:.1},{:.1}", x, y)
+    };
+    std::iter::once((0, 0)).chain(points.iter().copied()).map(scale).collect::<Vec<_>>().join(" ")
+}
+
+/// Cumulative score over the puzzle's timed finds, with the Genius line marked. Nothing shows
+/// until there are two timed finds.
+pub fn view(game: &GameState, stats: Option<&Stats>) -> Html {
+    let points = game.score_over_time();
+    if points.len() < 2 {
+        return html! {};
+    }
+    let max_score = game.max_score();
+    let genius = RANKS.iter().find(|(rank, _)| *rank == GENIUS).map_or(0.0, |(_, needed)| *needed);
+    let genius_y = format!("{:.1}", HEIGHT - genius * HEIGHT);
+    let elapsed = points.last().map_or(0, |(elapsed, _)| *elapsed);
+    let mut caption = format!("{} points in {} of play", game.score(), blitz::clock(elapsed / 1000));
+    if let Some((_, Some(ms))) = game.reached(GENIUS) {
+        caption.push_str(&format!(", Genius at {}", blitz::clock(ms / 1000)));
+    }
+    if let Some(average) = stats.and_then(Stats::average_to_genius) {
+        caption.push_str(&format!(" (usually {})", blitz::clock(average / 1000)));
+    }
+    html! {
+        <figure class="score-chart">
+            <svg viewBox=format!("0 0 {} {}", WIDTH, HEIGHT) preserveAspectRatio="none" role="img" aria-label="Score over time">
+                <line class="score-chart-genius" x1="0" y1=genius_y.clone() x2=WIDTH.to_string() y2=genius_y />
+                <polyline class="score-chart-line" points=polyline(&points, max_score) />
+            </svg>
+            <figcaption>{ caption }</figcaption>
+        </figure>
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::chart::polyline;
+
+    #[test]
+    fn scales_to_the_chart() {
+        assert_eq!(polyline(&[(0, 7), (60_000, 14)], 28), "0.0,120.0 0.0,90.0 300.0,60.0");
+        assert_eq!(polyline(&[], 0), "0.0,120.0");
+    }
+}
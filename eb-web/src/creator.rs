@@ -0,0 +1,205 @@
+use crate::archive;
+use eb_core::{Dictionary, LetterRule, Wordlist, HIVE_SIZE};
+use std::collections::HashSet;
+use yew::{html, ChangeData, Component, ComponentLink, Html, InputData, ShouldRender};
+
+/// Every word that could answer some hive, generated by `eb-words --dictionary`
+const DICTIONARY: &str = include_str!("../dictionary.txt");
+
+pub fn dictionary() -> Dictionary {
+    Dictionary::parse(DICTIONARY)
+}
+
+/// Whether the bundled dictionary has every answer `rule` could give. It only keeps words with
+/// seven distinct letters, so rules that drop as many letters as they add are the ones it covers.
+pub fn solves(rule: &LetterRule) -> bool {
+    rule.distinct_letters() == Some(HIVE_SIZE)
+}
+
+/// The preset rules puzzles can be created and practised with in the browser
+pub fn rules() -> impl Iterator<Item = LetterRule> {
+    LetterRule::PRESETS.iter().copied().filter(solves)
+}
+
+/// A custom puzzle is shared as its letters, center first, after the name of its rule unless
+/// that's Emily's; that's also the key its progress is stored under, the same as for daily
+/// puzzles.
+pub fn share_code(center: char, outer: &str, rule: LetterRule) -> String {
+    if rule.is_emily() {
+        format!("{}{}", center, outer)
+    } else {
+        format!("{}-{}{}", rule.name(), center, outer)
+    }
+}
+
+/// Letters and rule from a `?puzzle=` code, if it names a valid hive and a preset rule the
+/// bundled dictionary can solve
+pub fn parse_code(code: &str) -> Option<(char, String, LetterRule)> {
+    let (rule, code) = match code.rsplit_once('-') {
+        Some((name, code)) => (LetterRule::from_name(name).filter(solves)?, code),
+        None => (LetterRule::EMILY, code),
+    };
+    let distinct = code.chars().collect::<HashSet<_>>();
+    if code.len() != HIVE_SIZE || distinct.len() != HIVE_SIZE || !code.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+    let mut letters = code.chars();
+    let center = letters.next()?;
+    Some((center, letters.collect(), rule))
+}
+
+/// Solves the puzzle named by `?puzzle=` in the page's query string
+pub fn requested_puzzle(search: &str) -> Option<Wordlist> {
+    let (center, outer, rule) = parse_code(&archive::query_param(search, "puzzle")?)?;
+    Some(dictionary().solve_with(center, &outer, rule))
+}
+
+pub enum Msg {
+    Letters(String),
+    Center(char),
+    Rule(LetterRule),
+}
+
+/// Lets a player pick seven letters and a center, and shows how many answers that hive has
+pub struct Creator {
+    link: ComponentLink<Self>,
+    dictionary: Dictionary,
+    letters: String,
+    center: Option<char>,
+    rule: LetterRule,
+}
+
+impl Creator {
+    fn hive(&self) -> Option<(char, String, LetterRule)> {
+        let center = self.center?;
+        let outer = self.letters.chars().filter(|c| *c != center).collect::<String>();
+        parse_code(&share_code(center, &outer, self.rule))
+    }
+
+    fn share(&self) -> Html {
+        let (center, outer, rule) = match self.hive() {
+            Some(hive) => hive,
+            None => return html! { <p class="creator-hint">{ "Pick seven different letters, then a center" }</p> },
+        };
+        let answers = self.dictionary.solve_with(center, &outer, rule).words.len();
+        if answers == 0 {
+            return html! { <p class="creator-hint">{ "No answers with those letters, try others" }</p> };
+        }
+        let location = yew::utils::window().location();
+        let url = format!(
+            "{}{}?puzzle={}",
+            location.origin().unwrap_or_default(),
+            location.pathname().unwrap_or_default(),
+            share_code(center, &outer, rule)
+        );
+        html! {
+            <div class="creator-share">
+                <p>{ format!("{} answers", answers) }</p>
+                <input class="creator-url" type="text" readonly=true value=url.clone() />
+                <a href=url>{ "Play it" }</a>
+            </div>
+        }
+    }
+}
+
+impl Component for Creator {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Creator {
+            link,
+            dictionary: dictionary(),
+            letters: String::new(),
+            center: None,
+            rule: LetterRule::EMILY,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Letters(letters) => {
+                let mut seen = HashSet::new();
+                self.letters = letters
+                    .chars()
+                    .map(|c| c.to_ascii_lowercase())
+                    .filter(|c| c.is_ascii_lowercase() && seen.insert(*c))
+                    .take(HIVE_SIZE)
+                    .collect();
+                if !self.center.is_some_and(|center| self.letters.contains(center)) {
+                    self.center = None;
+                }
+            }
+            Msg::Center(center) => self.center = Some(center),
+            Msg::Rule(rule) => self.rule = rule,
+        }
+        true
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        let centers = self.letters.chars().map(|letter| {
+            let onchange = self.link.callback(move |_: ChangeData| Msg::Center(letter));
+            html! {
+                <label class="creator-center">
+                    <input type="radio" name="creator-center" checked=self.center == Some(letter) onchange=onchange />
+                    { letter }
+                </label>
+            }
+        }).collect::<Html>();
+        let rules = rules().map(|rule| html! {
+            <option value={rule.name()} selected={rule == self.rule}>{ rule.label() }</option>
+        }).collect::<Html>();
+        let onchange_rule = self.link.batch_callback(|e: ChangeData| match e {
+            ChangeData::Select(select) => LetterRule::from_name(&select.value()).map(Msg::Rule),
+            _ => None,
+        });
+        html! {
+            <div class="creator-box">
+                <h3>{ "Create a puzzle" }</h3>
+                <input
+                    class="creator-letters"
+                    type="text"
+                    placeholder="Seven letters"
+                    value=self.letters.clone()
+                    oninput=self.link.callback(|e: InputData| Msg::Letters(e.value))
+                />
+                <div class="creator-centers">{ centers }</div>
+                <select class="creator-rule" onchange=onchange_rule>{ rules }</select>
+                { self.share() }
+            </div>
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::creator::{dictionary, parse_code, share_code};
+    use eb_core::LetterRule;
+
+    #[test]
+    fn codes() {
+        assert_eq!(parse_code("cgameok"), Some(('c', "gameok".to_string(), LetterRule::EMILY)));
+        let squared = share_code('c', "gameok", LetterRule::EMILY_SQUARED);
+        assert_eq!(squared, "emily-squared-cgameok");
+        assert_eq!(parse_code(&squared), Some(('c', "gameok".to_string(), LetterRule::EMILY_SQUARED)));
+        assert_eq!(parse_code("drop-3-cgameok"), None);
+        assert_eq!(parse_code("classic-cgameok"), None);
+        assert_eq!(parse_code("cgameoc"), None);
+        assert_eq!(parse_code("cgame"), None);
+        assert_eq!(parse_code("Cgameok"), None);
+    }
+
+    #[test]
+    fn bundled_dictionary_matches_daily_puzzle() {
+        let today: eb_core::Wordlist = serde_json::from_str(include_str!("../word-lists/2022-03-07.json")).unwrap();
+        let mut solved = dictionary().solve(today.center, &today.outer).words;
+        let mut expected = today.words.clone();
+        solved.sort();
+        expected.sort();
+        assert_eq!(solved, expected);
+    }
+}
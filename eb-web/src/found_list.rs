@@ -0,0 +1,308 @@
+use crate::reveal::defined_word;
+use crate::settings::SortOrder;
+use eb_core::{added_letters, dropped_letters, letter_list};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use yew::{html, Callback, ChangeData, Component, ComponentLink, Html, InputData, Properties, ShouldRender};
+
+/// Words per page in the compact layout, which fills columns the way the puzzle's own list does
+pub const PAGE_SIZE: usize = 18;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Grouping {
+    None,
+    Dropped,
+    Added,
+}
+
+impl Grouping {
+    const ALL: [Grouping; 3] = [Grouping::None, Grouping::Dropped, Grouping::Added];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Grouping::None => "none",
+            Grouping::Dropped => "dropped",
+            Grouping::Added => "added",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Grouping::None => "No groups",
+            Grouping::Dropped => "By dropped letter",
+            Grouping::Added => "By added letter",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Grouping> {
+        Grouping::ALL.iter().copied().find(|grouping| grouping.name() == name)
+    }
+
+    /// The group `word` falls in, as its heading; `None` while not grouping
+    fn heading(&self, grid: &HashSet<char>, word: &str) -> Option<(BTreeSet<char>, String)> {
+        let letters = match self {
+            Grouping::None => return None,
+            Grouping::Dropped => dropped_letters(grid, word),
+            Grouping::Added => added_letters(grid, word),
+        };
+        let heading = match (self, letters.is_empty()) {
+            (Grouping::Dropped, false) => format!("Without {}", letter_list(&letters)),
+            (Grouping::Dropped, true) => "Every hive letter".to_string(),
+            (_, false) => format!("Adds {}", letter_list(&letters)),
+            (_, true) => "Nothing added".to_string(),
+        };
+        Some((letters, heading))
+    }
+}
+
+/// A run of words under an optional heading
+#[derive(Debug, PartialEq)]
+pub struct Section<'a> {
+    pub heading: Option<String>,
+    pub words: Vec<&'a String>,
+}
+
+/// The words matching `filter`, sorted and then grouped. Groups come in letter order, with the
+/// words that have no such letter first.
+pub fn arrange<'a>(
+    words: &'a [String],
+    sort: SortOrder,
+    grouping: Grouping,
+    grid: &HashSet<char>,
+    filter: &str,
+) -> Vec<Section<'a>> {
+    let filter = filter.trim().to_lowercase();
+    let mut sections = BTreeMap::<BTreeSet<char>, Section>::new();
+    for word in sort.sort(words) {
+        if !word.contains(filter.as_str()) {
+            continue;
+        }
+        let (key, heading) = match grouping.heading(grid, word) {
+            Some((letters, heading)) => (letters, Some(heading)),
+            None => (BTreeSet::new(), None),
+        };
+        sections.entry(key).or_insert_with(|| Section { heading, words: vec![] }).words.push(word);
+    }
+    sections.into_values().collect()
+}
+
+/// The part of `sections` on page `page`, keeping the headings of the sections it cuts into
+pub fn paginate<'a>(sections: Vec<Section<'a>>, page: usize, size: usize) -> Vec<Section<'a>> {
+    let range = page * size..(page + 1) * size;
+    let mut seen = 0;
+    let mut paged = vec![];
+    for section in sections {
+        let start = seen;
+        seen += section.words.len();
+        let words = section
+            .words
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| range.contains(&(start + i)))
+            .map(|(_, word)| word)
+            .collect::<Vec<_>>();
+        if !words.is_empty() {
+            paged.push(Section { heading: section.heading, words });
+        }
+    }
+    paged
+}
+
+pub enum Msg {
+    Sort(SortOrder),
+    Group(Grouping),
+    Filter(String),
+    ToggleCompact,
+    Page(usize),
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub words: Vec<String>,
+    /// The hive's letters, for grouping
+    pub grid: HashSet<char>,
+    /// Who found each word, in a shared room
+    #[prop_or_default]
+    pub finders: HashMap<String, String>,
+    #[prop_or_default]
+    pub definitions: HashMap<String, String>,
+    pub sort: SortOrder,
+    pub onsort: Callback<SortOrder>,
+}
+
+/// The found words, with controls to sort, group and filter them. The sort order is a setting
+/// and so belongs to the parent; the rest only lasts as long as the list is open.
+pub struct FoundList {
+    props: Props,
+    link: ComponentLink<Self>,
+    grouping: Grouping,
+    filter: String,
+    compact: bool,
+    page: usize,
+}
+
+impl FoundList {
+    fn controls(&self) -> Html {
+        let sorts = SortOrder::ALL.iter().map(|sort| html! {
+            <option value=sort.name() selected={*sort == self.props.sort}>{ sort.label() }</option>
+        }).collect::<Html>();
+        let on_sort = self.link.batch_callback(|e: ChangeData| match e {
+            ChangeData::Select(select) => SortOrder::ALL.iter().copied().find(|sort| sort.name() == select.value()).map(Msg::Sort),
+            _ => None,
+        });
+        let groupings = Grouping::ALL.iter().map(|grouping| html! {
+            <option value=grouping.name() selected={*grouping == self.grouping}>{ grouping.label() }</option>
+        }).collect::<Html>();
+        let on_group = self.link.batch_callback(|e: ChangeData| match e {
+            ChangeData::Select(select) => Grouping::from_name(&select.value()).map(Msg::Group),
+            _ => None,
+        });
+        html! {
+            <div class="found-list-controls">
+                <input
+                    type="search"
+                    class="found-list-filter"
+                    placeholder="Filter"
+                    aria-label="Filter found words"
+                    value=self.filter.clone()
+                    oninput=self.link.callback(|e: InputData| Msg::Filter(e.value))
+                />
+                <select aria-label="Sort found words" onchange=on_sort>{ sorts }</select>
+                <select aria-label="Group found words" onchange=on_group>{ groupings }</select>
+                <label>
+                    <input type="checkbox" checked=self.compact onclick=self.link.callback(|_| Msg::ToggleCompact) />
+                    { "Compact" }
+                </label>
+            </div>
+        }
+    }
+
+    fn word(&self, word: &str) -> Html {
+        let definition = self.props.definitions.get(word).map(|definition| definition.as_str());
+        let word_html = defined_word(word, definition);
+        match self.props.finders.get(word) {
+            Some(finder) => html! { <li>{ word_html }<span class="wordlist-finder">{ finder }</span></li> },
+            None => html! { <li>{ word_html }</li> },
+        }
+    }
+
+    fn pager(&self, page: usize, pages: usize) -> Html {
+        if !self.compact || pages <= 1 {
+            return html! {};
+        }
+        html! {
+            <div class="found-list-pager">
+                <button type="button" aria-label="Previous page" disabled={page == 0} onclick=self.link.callback(move |_| Msg::Page(page.saturating_sub(1)))>{ "‹" }</button>
+                <span>{ format!("{} of {}", page + 1, pages) }</span>
+                <button type="button" aria-label="Next page" disabled={page + 1 >= pages} onclick=self.link.callback(move |_| Msg::Page(page + 1))>{ "›" }</button>
+            </div>
+        }
+    }
+}
+
+impl Component for FoundList {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        FoundList {
+            props,
+            link,
+            grouping: Grouping::None,
+            filter: String::new(),
+            compact: false,
+            page: 0,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Sort(sort) => {
+                self.props.onsort.emit(sort);
+                return false;
+            }
+            Msg::Group(grouping) => self.grouping = grouping,
+            Msg::Filter(filter) => self.filter = filter,
+            Msg::ToggleCompact => self.compact = !self.compact,
+            Msg::Page(page) => return std::mem::replace(&mut self.page, page) != page,
+        }
+        self.page = 0;
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        let changed = self.props != props;
+        self.props = props;
+        changed
+    }
+
+    fn view(&self) -> Html {
+        let sections = arrange(&self.props.words, self.props.sort, self.grouping, &self.props.grid, &self.filter);
+        let matching = sections.iter().map(|section| section.words.len()).sum::<usize>();
+        let pages = matching.div_ceil(PAGE_SIZE);
+        // The word count can shrink under the current page, e.g. when a new room starts
+        let page = self.page.min(pages.saturating_sub(1));
+        let (sections, class) = if self.compact {
+            (paginate(sections, page, PAGE_SIZE), "sb-wordlist-items-pag")
+        } else {
+            (sections, "sb-wordlist-items-pag single")
+        };
+        let items = sections.iter().map(|section| {
+            let heading = match &section.heading {
+                Some(heading) => html! { <li class="found-list-heading">{ heading }</li> },
+                None => html! {},
+            };
+            let words = section.words.iter().map(|word| self.word(word)).collect::<Html>();
+            html! { <>{ heading }{ words }</> }
+        }).collect::<Html>();
+        let empty = if matching == 0 && !self.props.words.is_empty() {
+            html! { <p class="found-list-empty">{ "No found words match" }</p> }
+        } else {
+            html! {}
+        };
+        let container = if self.compact { "found-list compact" } else { "found-list" };
+        html! {
+            <div class=container>
+                { self.controls() }
+                { empty }
+                <ul class=class>{ items }</ul>
+                { self.pager(page, pages) }
+            </div>
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::found_list::{arrange, paginate, Grouping};
+    use crate::settings::SortOrder;
+
+    fn words() -> Vec<String> {
+        ["gamecock", "mock", "lockage", "comae", "cameo"].iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn groups_by_dropped_letter() {
+        let words = words();
+        let grid = "cgameok".chars().collect();
+        let sections = arrange(&words, SortOrder::Alphabetical, Grouping::Dropped, &grid, "");
+        let headings = sections.iter().map(|section| section.heading.clone().unwrap()).collect::<Vec<_>>();
+        assert_eq!(headings, vec!["Every hive letter", "Without A, E and G", "Without G and K", "Without M"]);
+        assert_eq!(sections[2].words, vec!["cameo", "comae"]);
+
+        let filtered = arrange(&words, SortOrder::Found, Grouping::None, &grid, " OCK");
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].words, vec!["gamecock", "mock", "lockage"]);
+    }
+
+    #[test]
+    fn pages_keep_their_headings() {
+        let words = words();
+        let grid = "cgameok".chars().collect();
+        let sections = arrange(&words, SortOrder::Alphabetical, Grouping::Dropped, &grid, "");
+        let second = paginate(sections, 1, 3);
+        assert_eq!(second.len(), 2);
+        assert_eq!(second[0].heading.as_deref(), Some("Without G and K"));
+        assert_eq!(second[0].words, vec!["comae"]);
+        assert_eq!(second[1].words, vec!["lockage"]);
+    }
+}
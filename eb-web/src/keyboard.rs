@@ -1,7 +1,8 @@
-use std::collections::HashSet;
-use yew::{html, Callback, Component, ComponentLink, Html, Properties, ShouldRender};
+use crate::a11y;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use yew::{html, Callback, Component, ComponentLink, Html, KeyboardEvent, Properties, ShouldRender};
 use yew::services::ConsoleService;
-use yew::web_sys::console::log;
 
 pub enum Msg {
     Char(char),
@@ -10,11 +11,81 @@ pub enum Msg {
 
 const SHUFFLE: char = '↺';
 
+#[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Layout {
+    #[default]
+    Qwerty,
+    Azerty,
+    Qwertz,
+    Dvorak,
+    #[serde(rename = "abc")]
+    Alphabetical,
+}
+
+impl Layout {
+    pub const ALL: [Layout; 5] = [
+        Layout::Qwerty,
+        Layout::Azerty,
+        Layout::Qwertz,
+        Layout::Dvorak,
+        Layout::Alphabetical,
+    ];
+
+    /// Stable identifier, used both as the `<option>` value and in the settings record
+    pub fn name(&self) -> &'static str {
+        match self {
+            Layout::Qwerty => "qwerty",
+            Layout::Azerty => "azerty",
+            Layout::Qwertz => "qwertz",
+            Layout::Dvorak => "dvorak",
+            Layout::Alphabetical => "abc",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Layout::Qwerty => "QWERTY",
+            Layout::Azerty => "AZERTY",
+            Layout::Qwertz => "QWERTZ",
+            Layout::Dvorak => "Dvorak",
+            Layout::Alphabetical => "ABC",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Layout> {
+        Layout::ALL.iter().copied().find(|layout| layout.name() == name)
+    }
+
+    /// Letter rows, top to bottom. The shuffle key always ends the last row.
+    fn rows(&self) -> Vec<String> {
+        let rows: [&str; 3] = match self {
+            Layout::Qwerty => ["qwertyuiop", "asdfghjkl", "zxcvbnm"],
+            Layout::Azerty => ["azertyuiop", "qsdfghjklm", "wxcvbn"],
+            Layout::Qwertz => ["qwertzuiop", "asdfghjkl", "yxcvbnm"],
+            Layout::Dvorak => ["pyfgcrl", "aoeuidhtns", "qjkxbmwvz"],
+            Layout::Alphabetical => ["abcdefghi", "jklmnopqr", "stuvwxyz"],
+        };
+        let mut rows = rows.iter().map(|row| row.to_string()).collect::<Vec<_>>();
+        rows.last_mut().unwrap().push(SHUFFLE);
+        rows
+    }
+}
+
 #[derive(Clone, Properties, PartialEq, Debug)]
 pub struct Props {
-    pub purple: Option<char>,
+    /// Outside letters the word being typed already adds
+    pub purple: BTreeSet<char>,
+    /// How many outside letters an answer adds
+    #[prop_or(1)]
+    pub added: usize,
     pub grid: HashSet<char>,
     pub ontype: Callback<Msg>,
+    #[prop_or_default]
+    pub layout: Layout,
+    /// Remaining answers per added letter; `None` when hints are turned off
+    #[prop_or_default]
+    pub coverage: Option<HashMap<char, usize>>,
 }
 
 pub struct Keyboard {
@@ -46,42 +117,123 @@ impl Component for Keyboard {
     }
 
     fn view(&self) -> Html {
-        ConsoleService::log(&format!("props: {:?}", &self.props));
-        let rows = &["qwertyuiop".to_string(), "asdfghjkl".to_string(), format!("zxcvbnm{}", SHUFFLE)];
-        let keyboard = rows.iter().enumerate().flat_map(
-            |(row_idx, row)|row.chars().enumerate().map(
-                move |(col_idx, key)| make_hexagon(key, row_idx, col_idx, &self.link, self.letter_status(key)))
+        ConsoleService::log(&format!("props: {:?}", self.props));
+        let grid = hex_grid(&self.props.layout.rows());
+        let keyboard = grid.slots.iter().map(
+            |slot| make_hexagon(slot, grid.columns, &self.link, self.props.letter_status(slot.key), self.props.badge(slot.key))
         ).collect::<Html>();
         html! {
-            <div class="keyboard-container">
+            <div class="keyboard-container" role="group" aria-label="Keyboard" style={ grid.container_style() }>
+                <svg class="keyboard-patterns" aria-hidden="true">
+                    <defs>
+                        <pattern id="added-letter-stripes" width="16" height="16" patternUnits="userSpaceOnUse" patternTransform="rotate(45)">
+                            <rect class="stripe-base" width="16" height="16" />
+                            <rect class="stripe" width="7" height="16" />
+                        </pattern>
+                    </defs>
+                </svg>
                 { keyboard }
             </div>
         }
     }
 }
 
-impl Keyboard {
+impl Props {
+    /// Outside letters stay open until the word adds as many as an answer can
     fn letter_status(&self, letter: char) -> Status {
         if letter == SHUFFLE {
             return Status::Normal
         }
-        if self.props.grid.contains(&letter) {
+        if self.grid.contains(&letter) {
             return Status::InGrid
         }
-        match self.props.purple {
-            None => Status::Normal,
-            Some(purple) if letter == purple => Status::Purple,
-            Some(_disabled) => Status::Disabled
+        if self.purple.contains(&letter) {
+            return Status::Purple
+        }
+        if self.purple.len() >= self.added {
+            return Status::Disabled
+        }
+        match &self.coverage {
+            Some(coverage) if !coverage.contains_key(&letter) => Status::Disabled,
+            _ => Status::Normal,
+        }
+    }
+
+    /// Count shown on an outside letter while hints are on
+    fn badge(&self, letter: char) -> Option<usize> {
+        if !self.purple.is_empty() {
+            return None
+        }
+        self.coverage.as_ref()?.get(&letter).copied()
+    }
+}
+
+/// Height of a pointy-top hexagon relative to its width
+const HEX_ASPECT: f64 = 120.0 / 103.92304845413263;
+
+/// A key's position, measured in key widths from the left and in rows from the top
+#[derive(Debug, PartialEq)]
+struct Slot {
+    key: char,
+    x: f64,
+    row: usize,
+}
+
+struct Grid {
+    slots: Vec<Slot>,
+    /// Width of the whole keyboard in key widths
+    columns: f64,
+    rows: usize,
+}
+
+impl Grid {
+    fn container_style(&self) -> String {
+        // Rows overlap by a quarter of a hexagon, so only the last one contributes its full height
+        let height = (self.rows as f64 * 0.75 + 0.25) * HEX_ASPECT / self.columns * 100.0;
+        format!("height: calc({h:.3}vw)", h = height)
+    }
+}
+
+/// Lays out rows of keys on a hex grid: each row is centered under the widest one, and nudged
+/// by half a key when needed so that neighbouring rows interlock instead of stacking.
+fn hex_grid(rows: &[String]) -> Grid {
+    let widest = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+    let mut slots = vec![];
+    let mut columns: f64 = 0.0;
+    for (row_idx, row) in rows.iter().enumerate() {
+        let len = row.chars().count();
+        // offset in half-keys; on a hex grid odd rows sit on odd half-keys
+        let mut offset = widest - len;
+        if offset % 2 != row_idx % 2 {
+            offset = if offset > 0 { offset - 1 } else { offset + 1 };
         }
+        let start = offset as f64 / 2.0;
+        columns = columns.max(start + len as f64);
+        slots.extend(row.chars().enumerate().map(|(col_idx, key)| Slot {
+            key,
+            x: start + col_idx as f64,
+            row: row_idx,
+        }));
+    }
+    Grid {
+        slots,
+        columns,
+        rows: rows.len(),
     }
 }
 
-fn compute_transform(row: usize, col: usize) -> String {
-    let y: i32 = 75 * row as i32;
-    let x = (col * 100) + row * 50;
-    format!("transform: translateX({x}%) translateY({y}%)", x=x,y=y)
+fn compute_transform(slot: &Slot, columns: f64) -> String {
+    let y = 75 * slot.row;
+    let x = slot.x * 100.0;
+    format!(
+        "width: {w:.3}%; transform: translateX({x}%) translateY({y}%)",
+        w = 100.0 / columns,
+        x = x,
+        y = y
+    )
 }
 
+#[derive(Debug, PartialEq)]
 enum Status {
     InGrid,
     Normal,
@@ -98,18 +250,116 @@ impl Status {
             Status::Purple => "purple-letter"
         }
     }
+
+    /// Drawn on the key so its state doesn't rest on colour alone: a dot for hive letters and a
+    /// plus for the added letter, on top of its stripes. Unavailable keys get a dashed outline
+    /// from the stylesheet instead.
+    fn marker(&self) -> Html {
+        match self {
+            Status::InGrid => html! { <circle class="key-marker" cx="50%" cy="76%" r="6" /> },
+            Status::Purple => html! { <text class="key-marker" x="24%" y="30%" dy="0.35em">{ "+" }</text> },
+            Status::Normal | Status::Disabled => html! {},
+        }
+    }
+
+    /// Spoken after the letter, standing in for the colour
+    fn description(&self) -> Option<&'static str> {
+        match self {
+            Status::InGrid => Some("in the hive"),
+            Status::Normal => None,
+            Status::Disabled => Some("unavailable"),
+            Status::Purple => Some("added letter"),
+        }
+    }
 }
 
+fn key_label(letter: char, status: &Status, badge: Option<usize>) -> String {
+    if letter == SHUFFLE {
+        return "Shuffle".to_string();
+    }
+    let mut label = letter.to_ascii_uppercase().to_string();
+    if let Some(description) = status.description() {
+        label = format!("{}, {}", label, description);
+    }
+    match badge {
+        Some(1) => format!("{}, 1 answer left", label),
+        Some(count) => format!("{}, {} answers left", label, count),
+        None => label,
+    }
+}
 
-fn make_hexagon(letter: char, row: usize, col: usize, link: &ComponentLink<Keyboard>, status: Status) -> Html {
+
+fn make_hexagon(slot: &Slot, columns: f64, link: &ComponentLink<Keyboard>, status: Status, badge: Option<usize>) -> Html {
     // points
     // 0,30 51.96,0 120,30
+    let letter = slot.key;
     let class = format!("keyboard-letter {}-text", status.class());
+    let label = key_label(letter, &status, badge);
+    let disabled = matches!(status, Status::Disabled).to_string();
+    let onkeydown = link.batch_callback(move |e: KeyboardEvent| a11y::press_or_move(&e).then_some(letter));
+    let badge = match badge {
+        Some(count) => html! { <text class="keyboard-badge" x="78%" y="30%" dy="0.35em">{ count }</text> },
+        None => html! {},
+    };
     html! {
-        <svg class="keyboard-cell" onclick={link.callback(move |_|letter)} style={ compute_transform(row, col) } viewBox="0 0 103.92304845413263 120">
+        <svg class="keyboard-cell" role="button" tabindex="0" aria-label=label aria-disabled=disabled onclick={link.callback(move |_|letter)} onkeydown=onkeydown style={ compute_transform(slot, columns) } viewBox="0 0 103.92304845413263 120">
             <polygon class={status.class()} points="0,30 0,90 51.96152422706631,120 103.92304845413263,90 103.92304845413263,30 51.96152422706631,0" stroke="white" stroke-width="7.5">
             </polygon>
             <text class={class} x="50%" y="50%" dy="0.35em">{ letter }</text>
+            { status.marker() }
+            { badge }
         </svg>
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::keyboard::{hex_grid, key_label, Layout, Props, Status, SHUFFLE};
+    use yew::Callback;
+
+    #[test]
+    fn qwerty_keeps_staggered_rows() {
+        let grid = hex_grid(&Layout::Qwerty.rows());
+        assert_eq!(grid.columns, 10.0);
+        let starts = (0..3)
+            .map(|row| grid.slots.iter().find(|slot| slot.row == row).unwrap().x)
+            .collect::<Vec<_>>();
+        assert_eq!(starts, vec![0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn rows_always_interlock() {
+        for layout in Layout::ALL.iter() {
+            let grid = hex_grid(&layout.rows());
+            for slot in &grid.slots {
+                let half_keys = (slot.x * 2.0) as usize;
+                assert_eq!(half_keys % 2, slot.row % 2, "{:?} {:?}", layout, slot);
+            }
+            assert_eq!(grid.slots.iter().filter(|slot| slot.key.is_alphabetic()).count(), 26);
+        }
+    }
+
+    #[test]
+    fn keys_say_what_their_colour_shows() {
+        assert_eq!(key_label('q', &Status::Disabled, None), "Q, unavailable");
+        assert_eq!(key_label('r', &Status::Normal, Some(3)), "R, 3 answers left");
+        assert_eq!(key_label(SHUFFLE, &Status::Normal, None), "Shuffle");
+    }
+
+    #[test]
+    fn keys_stay_open_for_every_added_letter() {
+        let mut props = Props {
+            purple: "s".chars().collect(),
+            added: 2,
+            grid: "cgameok".chars().collect(),
+            ontype: Callback::noop(),
+            layout: Layout::Qwerty,
+            coverage: None,
+        };
+        assert_eq!(props.letter_status('s'), Status::Purple);
+        assert_eq!(props.letter_status('d'), Status::Normal);
+        assert_eq!(props.letter_status('c'), Status::InGrid);
+        props.purple.insert('d');
+        assert_eq!(props.letter_status('r'), Status::Disabled);
+    }
+}
@@ -0,0 +1,246 @@
+use crate::archive;
+use eb_core::leaderboard::{RegisterRequest, Registration, Standing, Summary, Tables};
+use eb_core::Wordlist;
+use serde::de::DeserializeOwned;
+use yew::format::{Nothing, Text};
+use yew::services::fetch::{FetchService, FetchTask, Request, Response};
+use yew::web_sys::Storage;
+use yew::{html, Callback, Component, ComponentLink, Html, InputData, Properties, ShouldRender};
+
+/// Where `eb-leaderboard` listens when run locally; `?leaderboard=` points the app elsewhere
+pub const DEFAULT_SERVER: &str = "http://localhost:9002";
+
+const REGISTRATION_KEY: &str = "leaderboard-player";
+
+fn server() -> String {
+    let search = yew::utils::window().location().search().unwrap_or_default();
+    archive::query_param(&search, "leaderboard").unwrap_or_else(|| DEFAULT_SERVER.to_string())
+}
+
+fn local_storage() -> Option<Storage> {
+    yew::utils::window().local_storage().ok()?
+}
+
+/// Sends `request` and hands the decoded JSON reply, or the server's error message, to `callback`
+fn send<IN, T>(request: Request<IN>, callback: Callback<Result<T, String>>) -> Option<FetchTask>
+where
+    IN: Into<Text>,
+    T: DeserializeOwned + 'static,
+{
+    let handler = move |response: Response<Text>| {
+        let ok = response.status().is_success();
+        let body = response.into_body().unwrap_or_default();
+        let result = if ok {
+            serde_json::from_str(&body).map_err(|e| e.to_string())
+        } else {
+            Err(serde_json::from_str::<String>(&body).unwrap_or_else(|_| "The leaderboard is unavailable".into()))
+        };
+        callback.emit(result)
+    };
+    FetchService::fetch(request, handler.into()).ok()
+}
+
+fn post(path: &str, body: &impl serde::Serialize) -> Request<Text> {
+    Request::post(format!("{}{}", server(), path))
+        .header("Content-Type", "application/json")
+        .body(Ok(serde_json::to_string(body).unwrap()))
+        .unwrap()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Table {
+    Daily,
+    Weekly,
+    AllTime,
+}
+
+impl Table {
+    const ALL: [Table; 3] = [Table::Daily, Table::Weekly, Table::AllTime];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Table::Daily => "Today",
+            Table::Weekly => "This week",
+            Table::AllTime => "All time",
+        }
+    }
+
+    fn rows<'a>(&self, tables: &'a Tables) -> &'a [Standing] {
+        match self {
+            Table::Daily => &tables.daily,
+            Table::Weekly => &tables.weekly,
+            Table::AllTime => &tables.all_time,
+        }
+    }
+}
+
+pub enum Msg {
+    Name(String),
+    Register,
+    Registered(Result<Registration, String>),
+    Submit,
+    Submitted(Result<(), String>),
+    Loaded(Result<Tables, String>),
+    Show(Table),
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    /// The daily puzzle being played
+    pub date: String,
+    pub wordlist: Wordlist,
+    pub words: Vec<String>,
+}
+
+/// Daily, weekly and all-time tables from `eb-leaderboard`, and a button to post today's score
+pub struct Leaderboard {
+    link: ComponentLink<Self>,
+    props: Props,
+    name: String,
+    registration: Option<Registration>,
+    tables: Option<Tables>,
+    showing: Table,
+    status: Option<String>,
+    task: Option<FetchTask>,
+}
+
+impl Leaderboard {
+    fn load(&mut self) {
+        let request = Request::get(format!("{}/leaderboard?date={}", server(), self.props.date))
+            .body(Nothing)
+            .unwrap();
+        self.task = send(request, self.link.callback(Msg::Loaded));
+    }
+
+    fn submit(&mut self) {
+        let registration = match &self.registration {
+            Some(registration) => registration,
+            None => return,
+        };
+        let signed = Summary::new(&registration.player, &self.props.date, &self.props.wordlist, &self.props.words).sign(&registration.key);
+        self.task = send(post("/scores", &signed), self.link.callback(Msg::Submitted));
+    }
+
+    fn account(&self) -> Html {
+        if self.registration.is_some() {
+            return html! {
+                <button type="button" onclick=self.link.callback(|_| Msg::Submit)>
+                    { format!("Post today’s {} words", self.props.words.len()) }
+                </button>
+            };
+        }
+        html! {
+            <div class="leaderboard-join">
+                <input
+                    type="text"
+                    placeholder="Your name"
+                    value=self.name.clone()
+                    oninput=self.link.callback(|e: InputData| Msg::Name(e.value))
+                />
+                <button type="button" onclick=self.link.callback(|_| Msg::Register)>{ "Join the leaderboard" }</button>
+            </div>
+        }
+    }
+}
+
+impl Component for Leaderboard {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let registration = local_storage()
+            .and_then(|storage| storage.get_item(REGISTRATION_KEY).ok()?)
+            .and_then(|json| serde_json::from_str(&json).ok());
+        let mut leaderboard = Leaderboard {
+            link,
+            props,
+            name: String::new(),
+            registration,
+            tables: None,
+            showing: Table::Daily,
+            status: None,
+            task: None,
+        };
+        leaderboard.load();
+        leaderboard
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Name(name) => self.name = name,
+            Msg::Register => {
+                let request = post("/register", &RegisterRequest { name: self.name.clone() });
+                self.task = send(request, self.link.callback(Msg::Registered));
+            }
+            Msg::Registered(Ok(registration)) => {
+                if let Some(storage) = local_storage() {
+                    let _ = storage.set_item(REGISTRATION_KEY, &serde_json::to_string(&registration).unwrap());
+                }
+                self.registration = Some(registration);
+                self.submit();
+            }
+            Msg::Submit => self.submit(),
+            Msg::Submitted(Ok(())) => self.load(),
+            Msg::Loaded(Ok(tables)) => {
+                self.task = None;
+                self.status = None;
+                self.tables = Some(tables);
+            }
+            Msg::Registered(Err(message)) | Msg::Submitted(Err(message)) | Msg::Loaded(Err(message)) => {
+                self.task = None;
+                self.status = Some(message);
+            }
+            Msg::Show(table) => self.showing = table,
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props == props {
+            return false;
+        }
+        let reload = self.props.date != props.date;
+        self.props = props;
+        if reload {
+            self.load();
+        }
+        true
+    }
+
+    fn view(&self) -> Html {
+        let tabs = Table::ALL.iter().map(|table| {
+            let table = *table;
+            let class = if table == self.showing { "leaderboard-tab selected" } else { "leaderboard-tab" };
+            html! { <button type="button" class=class onclick=self.link.callback(move |_| Msg::Show(table))>{ table.label() }</button> }
+        }).collect::<Html>();
+        let rows = match &self.tables {
+            Some(tables) if self.showing.rows(tables).is_empty() => html! { <p>{ "No scores yet" }</p> },
+            Some(tables) => {
+                let rows = self.showing.rows(tables).iter().enumerate().map(|(place, standing)| html! {
+                    <tr>
+                        <td>{ place + 1 }</td>
+                        <td>{ &standing.name }</td>
+                        <td>{ format!("{} points", standing.points) }</td>
+                        <td>{ format!("{} words", standing.words) }</td>
+                    </tr>
+                }).collect::<Html>();
+                html! { <table class="leaderboard-table">{ rows }</table> }
+            }
+            None if self.task.is_some() => html! { <p>{ "Loading…" }</p> },
+            None => html! {},
+        };
+        let status = match &self.status {
+            Some(status) => html! { <p class="leaderboard-status">{ status }</p> },
+            None => html! {},
+        };
+        html! {
+            <div class="leaderboard-box">
+                <h3>{ "Leaderboard" }</h3>
+                <div class="leaderboard-tabs">{ tabs }</div>
+                { rows }
+                { status }
+                { self.account() }
+            </div>
+        }
+    }
+}
@@ -1,87 +1,515 @@
+// `html!` expands component props into statements clippy flags as no-ops.
+#![allow(clippy::unnecessary_operation)]
+
+mod a11y;
+mod archive;
+mod blitz;
+mod chart;
+mod creator;
+mod found_list;
 mod keyboard;
+mod leaderboard;
+mod practice;
+mod race;
+mod reveal;
+mod settings;
+mod theme;
 
+use eb_core::room::RoomKind;
+use eb_core::{Action, Completion, Difficulty, Event, Events, GameState, HintKind, LetterRule, Wordlist};
+use found_list::FoundList;
 use keyboard::Keyboard;
-use gloo_timers::callback::Timeout;
-use rand::prelude::SliceRandom;
-use serde::Deserialize;
-use std::collections::HashSet;
+use practice::Mode;
+use settings::{Settings, SortOrder};
+use std::collections::{BTreeSet, HashMap};
+use gloo_events::{EventListener, EventListenerOptions};
+use blitz::Blitz;
+use gloo_timers::callback::{Interval, Timeout};
 use wasm_bindgen::JsCast;
-use web_sys::HtmlElement;
+use web_sys::{Element, HtmlElement, InputEvent};
 use yew::prelude::*;
+use yew::services::fetch::FetchTask;
 use yew::services::keyboard::KeyListenerHandle;
 use yew::services::{ConsoleService, KeyboardService};
 use yew::web_sys;
 use yew::web_sys::Storage;
-use crate::web_sys::console::log;
 
 enum Msg {
-    PushLetter(char),
+    Game(Action),
     ToggleWords,
-    Backspace,
     Keyboard,
-    Submit,
+    ToggleSettings,
+    ToggleHints,
+    ToggleClassic,
+    UpdateSettings(Settings),
+    SetSort(SortOrder),
+    Reveal,
+    PuzzleLoaded(Option<Wordlist>),
+    NewerPuzzle(Option<Wordlist>),
+    ToggleYesterday,
+    UncoverYesterday,
+    YesterdayLoaded(Option<Wordlist>),
+    ToggleCreator,
+    TogglePractice,
+    ToggleRace,
+    ToggleLeaderboard,
+    ToggleBlitz,
+    SetBlitzSeconds(u64),
+    StartBlitz,
+    BlitzTick,
+    EndBlitz,
+    Race(race::Msg),
+    SetDifficulty(Difficulty),
+    SetRule(LetterRule),
+    NewHive,
     ClearMessage,
-    Shuffle,
     OtherKeypress,
 }
 
-#[derive(Deserialize)]
-struct Wordlist {
-    center: char,
-    outer: String,
-    words: Vec<String>,
-}
-
-impl Wordlist {
-    fn to_set(&self) -> HashSet<char> {
-        self.outer.chars().chain(Some(self.center)).collect()
-    }
-}
-
 const TODAY: &str = include_str!("../word-lists/today.json");
 
+/// Off-screen input that lets mobile players type with their OS keyboard.
+const HIDDEN_INPUT: &str = "hiddeninput";
+
 struct SpellingBee {
     // `ComponentLink` is like a reference to a component.
     // It can be used to send messages to the component
     link: ComponentLink<Self>,
-    letters: Vec<char>,
-    center: char,
-    found_words: Vec<String>,
-    current_word: String,
-    handle: KeyListenerHandle,
-    wordlist: Wordlist,
+    game: GameState,
+    _handle: KeyListenerHandle,
+    // Kept alive so the hidden input keeps turning spaces into shuffles
+    before_input: Option<EventListener>,
     local_storage: Storage,
     message: Option<String>,
+    /// Read out by screen readers whenever it changes
+    announcement: String,
     wordlist_visible: bool,
+    settings: Settings,
+    settings_visible: bool,
+    hints_visible: bool,
+    /// Set while an archived puzzle requested through `?date=` is being fetched
+    loading: bool,
+    puzzle_task: Option<FetchTask>,
+    yesterday: Option<Wordlist>,
+    yesterday_visible: bool,
+    /// Set once covered answers have been asked for
+    yesterday_uncovered: bool,
+    yesterday_task: Option<FetchTask>,
+    creator_visible: bool,
+    mode: Mode,
+    /// The Emily puzzle put aside while its hive is played by the regular rules
+    daily: Option<Wordlist>,
+    practice_visible: bool,
+    difficulty: Difficulty,
+    /// The rule practice hives are generated for
+    rule: LetterRule,
+    race: race::Race,
+    race_visible: bool,
+    leaderboard_visible: bool,
+    blitz: Option<Blitz>,
+    blitz_visible: bool,
+    blitz_seconds: u64,
 }
 
 impl SpellingBee {
     fn callback_for<T>(&self, letter: char) -> Callback<T> {
-        self.link.callback(move |_| Msg::PushLetter(letter))
+        self.link.callback(move |_| Msg::Game(Action::Push(letter)))
     }
 
-    fn grid(&self) -> HashSet<char> {
-        let mut letters: HashSet<char> = self.letters.iter().cloned().collect();
-        letters.insert(self.center);
-        letters
+    /// Presses a focused hive cell or button from the keyboard
+    fn press_for(&self, msg: impl Fn() -> Msg + 'static) -> Callback<KeyboardEvent> {
+        self.link.batch_callback(move |e: KeyboardEvent| a11y::press_or_move(&e).then(&msg))
     }
 
-    fn purple(&self) -> Option<char> {
-        let grid = self.grid();
-
-        let purple = self.current_word.chars().find(|letter|!grid.contains(letter));
-        ConsoleService::info(&format!("grid: {:?}, word: {}, pruple: {:?}", &grid, &self.current_word, purple));
+    fn purple(&self) -> BTreeSet<char> {
+        let purple = self.game.purple();
+        ConsoleService::info(&format!("grid: {:?}, word: {}, pruple: {:?}", self.game.grid(), self.game.current_word(), purple));
         purple
     }
-}
 
-fn key(c: char, letters: &[char]) -> String {
-    let mut s = String::new();
-    s.push(c);
-    for c in letters {
-        s.push(*c);
+    /// Persists and shows whatever the last action changed
+    fn handle(&mut self, events: Events) {
+        for event in events {
+            match event {
+                Event::Found(word) => {
+                    self.game.stamp(js_sys::Date::now() as u64);
+                    if self.mode.saves_progress() {
+                        self.local_storage
+                            .set_item(&self.game.wordlist().storage_key(), &self.game.progress())
+                            .unwrap();
+                    }
+                    if self.mode.is_shared() {
+                        self.race.submit(&word);
+                    }
+                    let first = self.game.found_words().len() == 1;
+                    let points = self.game.wordlist().points(&word);
+                    practice::record_find(&self.local_storage, self.mode, points, first);
+                    let latest = self.game.found_words().len() - 1;
+                    if let Some((i, Some(ms))) = self.game.reached(chart::GENIUS) {
+                        if i == latest && !self.game.revealed() {
+                            practice::record_genius(&self.local_storage, self.mode, ms);
+                        }
+                    }
+                    self.announce(format!("{} accepted, {} points", word, points));
+                }
+                Event::Completed => self.complete(),
+                Event::Rejected(rejection) => {
+                    self.announce(format!("Rejected: {}", rejection.message()));
+                    self.flash(rejection.message());
+                }
+                Event::Revealed if self.mode.saves_progress() => {
+                    self.local_storage.set_item(&self.game.wordlist().revealed_key(), "true").unwrap();
+                }
+                Event::Revealed => (),
+                Event::Hinted(hint) => {
+                    if self.mode.saves_progress() {
+                        self.local_storage.set_item(&self.game.wordlist().hints_key(), &self.game.hints_json()).unwrap();
+                    }
+                    self.announce(format!("Hint: {}", hint.text(self.game.wordlist())));
+                }
+            }
+        }
+    }
+
+    /// Records a finished puzzle. Only daily puzzles and practice hives keep stats; anywhere else
+    /// the moment just gets a message.
+    fn complete(&mut self) {
+        let message = "Queen Bee! Every word found".to_string();
+        self.announce(message.clone());
+        if !self.mode.keeps_stats() {
+            self.flash(message);
+            return;
+        }
+        let wordlist = self.game.wordlist();
+        let seconds = self.game.play_time().map(|ms| ms / 1000);
+        let completion = Completion::new(&self.game, seconds);
+        self.local_storage
+            .set_item(&wordlist.completed_key(), &serde_json::to_string(&completion).unwrap())
+            .unwrap();
+        practice::record_completion(&self.local_storage, self.mode, seconds);
+    }
+
+    /// The record of a finished puzzle, for puzzles finished before there were records too
+    fn completion(&self) -> Option<Completion> {
+        if !self.mode.keeps_stats() || !self.game.is_complete() {
+            return None;
+        }
+        let stored = self.local_storage.get_item(&self.game.wordlist().completed_key()).unwrap();
+        match stored.and_then(|json| serde_json::from_str(&json).ok()) {
+            Some(completion) => Some(completion),
+            None if !self.game.revealed() => Some(Completion::new(&self.game, None)),
+            None => None,
+        }
+    }
+
+    fn completion_view(&self) -> Html {
+        let completion = match self.completion() {
+            Some(completion) => completion,
+            None => return html! {},
+        };
+        let wordlist = self.game.wordlist();
+        let puzzle = match (&wordlist.date, self.mode) {
+            (_, Mode::Practice) => "practice".to_string(),
+            (Some(date), Mode::Classic) => format!("{} classic", date),
+            (Some(date), _) => date.clone(),
+            (None, _) => wordlist.storage_key().to_uppercase(),
+        };
+        let time = match completion.time() {
+            Some(time) => format!(" in {}", time),
+            None => String::new(),
+        };
+        let rarest = match completion.rarest {
+            Some((letter, 1)) => html! { <li>{ format!("Rarest added letter: {}, in a single answer", letter.to_ascii_uppercase()) }</li> },
+            Some((letter, count)) => html! { <li>{ format!("Rarest added letter: {}, in {} answers", letter.to_ascii_uppercase(), count) }</li> },
+            None => html! {},
+        };
+        let hints = match completion.hints {
+            0 => html! {},
+            1 => html! { <li>{ "With 1 hint token" }</li> },
+            hints => html! { <li>{ format!("With {} hint tokens", hints) }</li> },
+        };
+        let stats = practice::stats(&self.local_storage, self.mode);
+        let record = match stats.fastest_seconds {
+            Some(fastest) => format!("Queen Bee {} times, fastest in {}", stats.completed, blitz::clock(fastest)),
+            None => format!("Queen Bee {} times", stats.completed),
+        };
+        let next = match (&wordlist.date, self.mode) {
+            (_, Mode::Practice) => html! { <button type="button" onclick=self.link.callback(|_| Msg::NewHive)>{ "New hive" }</button> },
+            (Some(date), _) => {
+                let previous = archive::previous_day(date).map_or(html! {}, |previous| html! {
+                    <a href=archive::archive_url(&previous)>{ "Previous puzzle" }</a>
+                });
+                let today = if *date == archive::today() { html! {} } else { html! { <a href="?">{ "Today’s puzzle" }</a> } };
+                html! { <>{ previous }{ today }</> }
+            }
+            (None, _) => html! { <a href="?">{ "Today’s puzzle" }</a> },
+        };
+        html! {
+            <div class="completion-box">
+                <h3>{ "🐝 Queen Bee!" }</h3>
+                <p>{ format!("Every one of {} words, {} points{}", completion.words, completion.points, time) }</p>
+                <ul class="completion-facts">
+                    <li>{ format!("Longest word: {}", completion.longest) }</li>
+                    { rarest }
+                    { hints }
+                </ul>
+                <p class="completion-record">{ record }</p>
+                <input class="completion-share" type="text" readonly=true aria-label="Share your result" value=completion.share_text(&puzzle) />
+                <div class="completion-links">{ next }</div>
+            </div>
+        }
+    }
+
+    /// Has screen readers read out `text`
+    fn announce(&mut self, text: String) {
+        self.announcement = a11y::announcement(&self.announcement, text);
+    }
+
+    /// Shows `message` for a second
+    fn flash(&mut self, message: String) {
+        self.message = Some(message);
+        let link = self.link.clone();
+        Timeout::new(1000, move || link.send_message(Msg::ClearMessage)).forget();
+    }
+
+    /// Switches to `wordlist`, restoring whatever progress was stored for it
+    fn load(&mut self, wordlist: Wordlist) {
+        self.game = restore(&self.local_storage, wordlist, self.settings.reveal_old_puzzles);
+    }
+
+    fn practice_view(&self) -> Html {
+        if !self.practice_visible {
+            return html! {};
+        }
+        let options = Difficulty::ALL.iter().map(|difficulty| html! {
+            <option value={difficulty.name()} selected={*difficulty == self.difficulty}>{ difficulty.name() }</option>
+        }).collect::<Html>();
+        let onchange = self.link.batch_callback(|e: ChangeData| match e {
+            ChangeData::Select(select) => Difficulty::from_name(&select.value()).map(Msg::SetDifficulty),
+            _ => None,
+        });
+        let rules = creator::rules().map(|rule| html! {
+            <option value={rule.name()} selected={rule == self.rule}>{ rule.label() }</option>
+        }).collect::<Html>();
+        let onchange_rule = self.link.batch_callback(|e: ChangeData| match e {
+            ChangeData::Select(select) => LetterRule::from_name(&select.value()).map(Msg::SetRule),
+            _ => None,
+        });
+        let stats = practice::stats(&self.local_storage, Mode::Practice);
+        let mut summary = format!("{} hives played, {} words, {} points", stats.played, stats.words, stats.points);
+        if let Some(ms) = stats.average_to_genius() {
+            summary.push_str(&format!(", Genius in {} on average", blitz::clock(ms / 1000)));
+        }
+        let back = if self.mode == Mode::Practice {
+            html! { <a href="?">{ "Back to today’s puzzle" }</a> }
+        } else {
+            html! {}
+        };
+        html! {
+            <div class="practice-box">
+                <h3>{ "Practice" }</h3>
+                <div class="practice-controls">
+                    <select onchange=onchange>{ options }</select>
+                    <select onchange=onchange_rule>{ rules }</select>
+                    <button type="button" onclick=self.link.callback(|_| Msg::NewHive)>{ "New hive" }</button>
+                </div>
+                <div class="practice-stats">
+                    { summary }
+                </div>
+                { back }
+            </div>
+        }
+    }
+
+    /// Hint tokens to spend, and what the spent ones gave away, gathered by answer
+    fn hints_view(&self) -> Html {
+        if !self.hints_visible {
+            return html! {};
+        }
+        if !self.mode.keeps_stats() || self.blitz.is_some() {
+            return html! { <div class="hints-box">{ "Hint tokens are only for daily puzzles and practice hives" }</div> };
+        }
+        let game = &self.game;
+        let buttons = HintKind::ALL.iter().map(|&kind| html! {
+            <button type="button" disabled=game.hint(kind).is_none() onclick=self.link.callback(move |_| Msg::Game(Action::Hint(kind)))>
+                { kind.label() }
+            </button>
+        }).collect::<Html>();
+        let mut answers = Vec::<(&str, Vec<String>)>::new();
+        for hint in game.hints() {
+            let text = hint.text(game.wordlist());
+            match answers.iter_mut().find(|(word, _)| *word == hint.word) {
+                Some((_, texts)) => texts.push(text),
+                None => answers.push((&hint.word, vec![text])),
+            }
+        }
+        let answers = answers.iter().enumerate().map(|(i, (word, texts))| {
+            let name = if game.found_words().iter().any(|found| found == word) {
+                word.to_string()
+            } else {
+                format!("Answer {}", i + 1)
+            };
+            html! { <li><strong>{ name }</strong>{ format!(": {}", texts.join(" · ")) }</li> }
+        }).collect::<Html>();
+        html! {
+            <div class="hints-box">
+                <h3>{ "Hints" }</h3>
+                <p>{ format!("{} of {} hint tokens left", game.hints_left(), eb_core::TOKENS) }</p>
+                <div class="hints-controls">{ buttons }</div>
+                <ul class="hints-list">{ answers }</ul>
+            </div>
+        }
+    }
+
+    fn blitz_options(&self) -> Html {
+        if !self.blitz_visible {
+            return html! {};
+        }
+        let options = blitz::DURATIONS.iter().map(|seconds| html! {
+            <option value={seconds.to_string()} selected={*seconds == self.blitz_seconds}>{ blitz::clock(*seconds) }</option>
+        }).collect::<Html>();
+        let onchange = self.link.batch_callback(|e: ChangeData| match e {
+            ChangeData::Select(select) => select.value().parse().ok().map(Msg::SetBlitzSeconds),
+            _ => None,
+        });
+        let wordlist = match &self.blitz {
+            Some(blitz) => blitz.paused.0.wordlist(),
+            None => self.game.wordlist(),
+        };
+        let best = match blitz::best(&self.local_storage, wordlist, self.blitz_seconds) {
+            Some(best) => format!("Best on this puzzle: {} points", best),
+            None => "No rounds of this length on this puzzle yet".to_string(),
+        };
+        html! {
+            <div class="blitz-box">
+                <h3>{ "Blitz" }</h3>
+                <div class="blitz-controls">
+                    <select onchange=onchange>{ options }</select>
+                    <button type="button" onclick=self.link.callback(|_| Msg::StartBlitz)>{ "Start" }</button>
+                </div>
+                <div class="blitz-best">{ best }</div>
+            </div>
+        }
+    }
+
+    fn blitz_view(&self, blitz: &Blitz) -> Html {
+        let score = self.game.score();
+        if blitz.running() {
+            return html! {
+                <div class="blitz-bar">
+                    <span class="blitz-clock">{ blitz::clock(blitz.remaining) }</span>
+                    <span class="blitz-score">{ format!("{} points", score) }</span>
+                </div>
+            };
+        }
+        let best = match blitz.best {
+            Some(best) if best >= score => format!("Your best is {} points", best),
+            _ if score > 0 => "A new best for this puzzle!".to_string(),
+            _ => String::new(),
+        };
+        let words = self.game.found_words().iter().map(|word| html! { <li>{ word }</li> }).collect::<Html>();
+        html! {
+            <div class="blitz-box">
+                <h3>{ "Time’s up!" }</h3>
+                <p>{ format!("{} of {} words, {} points in {}", self.game.found_words().len(), self.game.wordlist().words.len(), score, blitz::clock(blitz.seconds)) }</p>
+                <p class="blitz-best">{ best }</p>
+                <ul class="blitz-words">{ words }</ul>
+                <div class="blitz-controls">
+                    <button type="button" onclick=self.link.callback(|_| Msg::StartBlitz)>{ "Play again" }</button>
+                    <button type="button" onclick=self.link.callback(|_| Msg::EndBlitz)>{ "Back to the puzzle" }</button>
+                </div>
+            </div>
+        }
+    }
+
+    fn yesterday_view(&self) -> Html {
+        if !self.yesterday_visible {
+            return html! {};
+        }
+        let yesterday = match &self.yesterday {
+            Some(yesterday) => yesterday,
+            None if self.yesterday_task.is_some() => return html! { <div class="yesterday-box">{ "Loading…" }</div> },
+            None => return html! { <div class="yesterday-box">{ "Yesterday’s puzzle isn’t available" }</div> },
+        };
+        let found = GameState::parse_progress(&progress(&self.local_storage, yesterday));
+        let words = if self.settings.cover_yesterday && !self.yesterday_uncovered {
+            html! { <button type="button" onclick=self.link.callback(|_| Msg::UncoverYesterday)>{ "Show the answers" }</button> }
+        } else {
+            let words = yesterday.words.iter().map(|word| {
+                if found.contains(word) {
+                    html! { <li class="yesterday-found">{ word }</li> }
+                } else {
+                    html! { <li>{ word }</li> }
+                }
+            }).collect::<Html>();
+            html! { <ul class="yesterday-words">{ words }</ul> }
+        };
+        let date = yesterday.date.clone().unwrap_or_default();
+        html! {
+            <div class="yesterday-box">
+                <h3 class="yesterday-letters">
+                    <span class="sb-input-bright">{ yesterday.center }</span>
+                    { &yesterday.outer }
+                </h3>
+                <div class="yesterday-summary">{ format!("You found {} of {} words", found.len(), yesterday.words.len()) }</div>
+                { words }
+                <a href={archive::archive_url(&date)}>{ "Play this puzzle" }</a>
+            </div>
+        }
+    }
+
+    fn hidden_input() -> Option<HtmlElement> {
+        web_sys::window()?
+            .document()?
+            .get_element_by_id(HIDDEN_INPUT)?
+            .dyn_into::<HtmlElement>()
+            .ok()
     }
-    s
+}
+
+/// Whether the puzzle's day ended long enough ago that its answers are no longer a spoiler
+fn is_stale(wordlist: &Wordlist) -> bool {
+    let date = match &wordlist.date {
+        Some(date) => date,
+        None => return false,
+    };
+    let published = js_sys::Date::parse(date);
+    !published.is_nan() && js_sys::Date::now() - published > reveal::AUTO_REVEAL_MS
+}
+
+/// Keys typed into any other field or control, such as a player name, a select or a definition's
+/// summary, are theirs
+fn from_form_field(e: &KeyboardEvent) -> bool {
+    e.target()
+        .and_then(|target| target.dyn_into::<Element>().ok())
+        .is_some_and(|el| {
+            el.id() != HIDDEN_INPUT && matches!(el.tag_name().as_str(), "INPUT" | "SELECT" | "TEXTAREA" | "BUTTON" | "SUMMARY")
+        })
+}
+
+/// Typing into the hidden input is handled by its own `input` events, so the
+/// window-level key handler must skip everything but Enter for that target.
+fn from_hidden_input(e: &KeyboardEvent) -> bool {
+    e.target()
+        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+        .is_some_and(|el| el.id() == HIDDEN_INPUT)
+}
+
+fn progress(local_storage: &Storage, wordlist: &Wordlist) -> String {
+    local_storage
+        .get_item(&wordlist.storage_key())
+        .unwrap()
+        .unwrap_or_default()
+}
+
+/// Picks up a puzzle where the player left it. Unless `reveal_old` is off, a puzzle that's a
+/// couple of days old comes with its answers showing.
+fn restore(local_storage: &Storage, wordlist: Wordlist, reveal_old: bool) -> GameState {
+    let progress = progress(local_storage, &wordlist);
+    let revealed = local_storage.get_item(&wordlist.revealed_key()).unwrap().is_some()
+        || (reveal_old && is_stale(&wordlist));
+    let hints = local_storage.get_item(&wordlist.hints_key()).unwrap().unwrap_or_default();
+    GameState::resume(wordlist, &progress, revealed).with_hints(&hints)
 }
 
 /*
@@ -102,21 +530,6 @@ fn wrap(html: Html) -> Html {
     }
 }*/
 
-fn error(word_list: &Wordlist, guess: &str) -> String {
-    let guess = guess.chars().collect::<HashSet<_>>();
-    let rules = word_list.to_set();
-    if guess.difference(&rules).count() > 1 {
-        format!("Too many new letters")
-    } else if rules.difference(&guess).count() > 1 {
-        format!(
-            "All letters except one must be included. Missing: {:?}",
-            rules.difference(&guess).collect::<Vec<_>>()
-        )
-    } else {
-        format!("Not in wordlist")
-    }
-}
-
 impl Component for SpellingBee {
     type Message = Msg;
     type Properties = ();
@@ -126,35 +539,69 @@ impl Component for SpellingBee {
         let handle = KeyboardService::register_key_down(
             &yew::utils::window(),
             link.callback(|e: KeyboardEvent| match e.key().as_str() {
-                " " => Msg::Shuffle,
-                ch if ch.len() == 1 => Msg::PushLetter(ch.chars().next().unwrap()),
-                "Backspace" => Msg::Backspace,
-                "Enter" => Msg::Submit,
+                // Already handled by a focused hive cell or key
+                _ if e.default_prevented() => Msg::OtherKeypress,
+                _ if from_form_field(&e) => Msg::OtherKeypress,
+                "Enter" => Msg::Game(Action::Submit),
+                _ if from_hidden_input(&e) => Msg::OtherKeypress,
+                " " => Msg::Game(Action::Shuffle),
+                ch if ch.len() == 1 => Msg::Game(Action::Push(ch.chars().next().unwrap())),
+                "Backspace" => Msg::Game(Action::Backspace),
                 _ => Msg::OtherKeypress,
             }),
         );
+        let search = web_sys::window().unwrap().location().search().unwrap_or_default();
+        let custom = creator::requested_puzzle(&search);
         let today: Wordlist = serde_json::from_str(TODAY).unwrap();
-        let letters: Vec<char> = today.outer.chars().collect();
-        let words: String = local_storage
-            .get_item(&key(today.center, &letters))
-            .unwrap()
-            .unwrap_or_default();
-        let found_words = words
-            .lines()
-            .map(|line| line.to_owned())
-            .collect::<Vec<_>>();
+        let settings = Settings::load(&local_storage);
+        settings.theme.apply();
+        let difficulty = practice::stored_difficulty(&local_storage);
+        let rule = practice::stored_rule(&local_storage);
+        let race = race::Race::new(&search);
+        let requested = archive::requested_date(&search);
+        let puzzle_task = requested
+            .as_ref()
+            .and_then(|date| archive::fetch(date, link.callback(Msg::PuzzleLoaded)));
+        // The bundled puzzle is only as new as the last deploy; a newer one may already be
+        // published, or pre-fetched by the service worker for offline play
+        let newer_task = match (&requested, &today.date) {
+            _ if custom.is_some() => None,
+            (None, Some(date)) if *date == archive::today() => None,
+            (None, _) => archive::fetch(&archive::today(), link.callback(Msg::NewerPuzzle)),
+            (Some(_), _) => None,
+        };
+        let game = restore(&local_storage, custom.unwrap_or(today), settings.reveal_old_puzzles);
 
         Self {
             link,
-            letters,
-            center: today.center,
-            found_words,
-            current_word: String::new(),
-            handle,
-            wordlist: today,
+            game,
+            _handle: handle,
+            before_input: None,
             local_storage,
             message: None,
+            announcement: String::new(),
             wordlist_visible: false,
+            settings,
+            settings_visible: false,
+            hints_visible: false,
+            loading: puzzle_task.is_some(),
+            puzzle_task: puzzle_task.or(newer_task),
+            yesterday: None,
+            yesterday_visible: false,
+            yesterday_uncovered: false,
+            yesterday_task: None,
+            creator_visible: false,
+            mode: Mode::Daily,
+            daily: None,
+            practice_visible: false,
+            difficulty,
+            rule,
+            race_visible: race.invited(),
+            leaderboard_visible: false,
+            blitz: None,
+            blitz_visible: false,
+            blitz_seconds: blitz::DEFAULT_SECONDS,
+            race,
         }
     }
 
@@ -162,46 +609,169 @@ impl Component for SpellingBee {
         match msg {
             Msg::ToggleWords => { self.wordlist_visible = !self.wordlist_visible },
             Msg::ClearMessage => self.message = None,
-            Msg::PushLetter(c) => {
-                self.current_word.push(c.to_ascii_lowercase());
+            Msg::Game(Action::Submit) if self.mode.is_shared() && !self.race.accepts_words() => {
+                self.flash("Words only count while the race is running".into());
             }
-            Msg::Shuffle => self.letters.shuffle(&mut rand::thread_rng()),
-            Msg::Backspace => {
-                self.current_word.pop();
+            Msg::Game(Action::Submit) if self.mode == Mode::Blitz && !self.blitz.as_ref().is_some_and(Blitz::running) => {
+                self.flash("Time’s up".into());
             }
-            Msg::Keyboard => {
-                let window = web_sys::window();
-                let document = window
-                    .unwrap()
-                    .document()
-                    .expect("window should have a document");
-                let _ = document
-                    .query_selector("#hiddeninput")
-                    .unwrap()
-                    .unwrap()
-                    .dyn_ref::<HtmlElement>()
-                    .unwrap()
-                    .focus();
+            Msg::Game(action) => {
+                let events = self.game.apply(action);
+                self.handle(events);
             }
-            Msg::Submit => {
-                if !self.wordlist.words.contains(&self.current_word) {
-                    self.message = Some(error(&self.wordlist, self.current_word.as_str()));
-                    let link = self.link.clone();
-                    Timeout::new(1000, move || link.send_message(Msg::ClearMessage)).forget();
-                    self.current_word.clear();
-                } else if self.found_words.contains(&self.current_word) {
-                    self.message = Some("Already found".into());
-                    self.current_word.clear();
-                } else {
-                    self.found_words
-                        .push(std::mem::take(&mut self.current_word))
+            Msg::ToggleSettings => self.settings_visible = !self.settings_visible,
+            Msg::ToggleHints => self.hints_visible = !self.hints_visible,
+            Msg::ToggleClassic => match self.mode {
+                Mode::Classic => {
+                    if let Some(daily) = self.daily.take() {
+                        self.mode = Mode::Daily;
+                        self.load(daily);
+                    }
+                }
+                Mode::Daily => match self.game.wordlist().classic.clone() {
+                    Some(classic) => {
+                        self.daily = Some(self.game.wordlist().clone());
+                        self.mode = Mode::Classic;
+                        self.load(*classic);
+                    }
+                    None => self.flash("This puzzle has no classic answers".to_string()),
+                },
+                _ => return false,
+            },
+            Msg::SetSort(sort) => {
+                self.settings.sort = sort;
+                self.settings.save(&self.local_storage);
+            }
+            Msg::UpdateSettings(settings) => {
+                if settings.theme != self.settings.theme {
+                    settings.theme.apply();
+                }
+                settings.save(&self.local_storage);
+                self.settings = settings;
+            }
+            // Revealing would hand over every answer to the whole room, or end a blitz round
+            Msg::Reveal if self.mode.is_shared() || self.mode == Mode::Blitz => return false,
+            Msg::Reveal => {
+                let confirmed = !self.settings.confirm_reveal
+                    || web_sys::window()
+                        .and_then(|window| window.confirm_with_message("Give up and reveal every answer?").ok())
+                        .unwrap_or(false);
+                if !confirmed {
+                    return false;
+                }
+                let events = self.game.apply(Action::Reveal);
+                self.handle(events);
+            }
+            Msg::PuzzleLoaded(wordlist) => {
+                self.loading = false;
+                self.puzzle_task = None;
+                match wordlist {
+                    Some(wordlist) => self.load(wordlist),
+                    None => self.message = Some("Couldn’t load that puzzle, here’s today’s".into()),
+                }
+            }
+            Msg::NewerPuzzle(wordlist) => {
+                self.puzzle_task = None;
+                // A practice hive started before the fetch finished is left alone, and so is the
+                // bundled puzzle once a word has been found in it or if the fetch is no newer
+                let bundled = self.game.wordlist().date.clone();
+                if let (Some(wordlist), Mode::Daily) = (wordlist, self.mode) {
+                    if archive::is_newer(wordlist.date.as_deref(), bundled.as_deref()) && self.game.found_words().is_empty() {
+                        self.load(wordlist);
+                    }
+                }
+            }
+            Msg::UncoverYesterday => self.yesterday_uncovered = true,
+            Msg::ToggleYesterday => {
+                self.yesterday_visible = !self.yesterday_visible;
+                self.yesterday_uncovered = false;
+                if self.yesterday_visible && self.yesterday.is_none() {
+                    let today = self.game.wordlist().date.clone().unwrap_or_else(archive::today);
+                    self.yesterday_task = archive::previous_day(&today)
+                        .and_then(|date| archive::fetch(&date, self.link.callback(Msg::YesterdayLoaded)));
+                }
+            }
+            Msg::ToggleCreator => self.creator_visible = !self.creator_visible,
+            Msg::TogglePractice => self.practice_visible = !self.practice_visible,
+            Msg::ToggleRace => self.race_visible = !self.race_visible,
+            Msg::ToggleLeaderboard => self.leaderboard_visible = !self.leaderboard_visible,
+            Msg::ToggleBlitz => self.blitz_visible = !self.blitz_visible,
+            Msg::SetBlitzSeconds(seconds) => self.blitz_seconds = seconds,
+            Msg::StartBlitz if self.mode.is_shared() => return false,
+            Msg::StartBlitz => {
+                let wordlist = self.game.wordlist().clone();
+                let fresh = GameState::new(wordlist.clone(), vec![], false);
+                let paused = match self.blitz.take() {
+                    Some(blitz) => {
+                        self.game = fresh;
+                        blitz.paused
+                    }
+                    None => (std::mem::replace(&mut self.game, fresh), self.mode),
+                };
+                let link = self.link.clone();
+                let interval = Interval::new(1000, move || link.send_message(Msg::BlitzTick));
+                let best = blitz::best(&self.local_storage, &wordlist, self.blitz_seconds);
+                self.blitz = Some(Blitz::new(self.blitz_seconds, js_sys::Date::now(), best, paused, interval));
+                self.mode = Mode::Blitz;
+                self.blitz_visible = false;
+            }
+            Msg::BlitzTick => {
+                let blitz = match &mut self.blitz {
+                    Some(blitz) => blitz,
+                    None => return false,
+                };
+                let score = self.game.score();
+                if blitz.tick(js_sys::Date::now()) && score > 0 && blitz.best.is_none_or(|best| score > best) {
+                    let key = self.game.wordlist().blitz_key(blitz.seconds);
+                    self.local_storage.set_item(&key, &score.to_string()).unwrap();
+                }
+            }
+            Msg::EndBlitz => {
+                if let Some(blitz) = self.blitz.take() {
+                    let (game, mode) = blitz.paused;
+                    self.game = game;
+                    self.mode = mode;
+                }
+            }
+            Msg::Race(msg) => {
+                if let Some((kind, wordlist)) = self.race.update(msg, &self.link) {
+                    self.blitz = None;
+                    self.mode = match kind {
+                        RoomKind::Race => Mode::Race,
+                        RoomKind::Team => Mode::Team,
+                    };
+                    self.wordlist_visible = false;
+                    self.game = GameState::new(wordlist, vec![], false);
+                }
+                if self.mode == Mode::Team {
+                    self.game.merge_found(self.race.found().iter().map(|found| &found.word));
+                }
+            }
+            Msg::SetDifficulty(difficulty) => {
+                self.difficulty = difficulty;
+                practice::store_difficulty(&self.local_storage, difficulty);
+            }
+            Msg::SetRule(rule) => {
+                self.rule = rule;
+                practice::store_rule(&self.local_storage, rule);
+            }
+            Msg::NewHive => match practice::generate(self.difficulty, self.rule) {
+                Some(wordlist) => {
+                    self.blitz = None;
+                    self.mode = Mode::Practice;
+                    self.wordlist_visible = false;
+                    self.game = GameState::new(wordlist, vec![], false);
+                }
+                None => self.message = Some("Couldn’t find a hive that fits, try another difficulty".into()),
+            },
+            Msg::YesterdayLoaded(wordlist) => {
+                self.yesterday_task = None;
+                self.yesterday = wordlist;
+            }
+            Msg::Keyboard => {
+                if let Some(input) = Self::hidden_input() {
+                    let _ = input.focus();
                 }
-                self.local_storage
-                    .set_item(
-                        &key(self.center, &self.letters),
-                        &self.found_words.join("\n"),
-                    )
-                    .unwrap();
             }
             Msg::OtherKeypress => (),
         };
@@ -214,17 +784,52 @@ impl Component for SpellingBee {
         false
     }
 
+    fn rendered(&mut self, first_render: bool) {
+        if !first_render {
+            return;
+        }
+        // `oninput` can't cancel the default action, so a space from the OS keyboard is caught
+        // before it reaches the input's value and turned into a shuffle like on desktop.
+        if let Some(input) = Self::hidden_input() {
+            let link = self.link.clone();
+            let listener = EventListener::new_with_options(
+                &input,
+                "beforeinput",
+                EventListenerOptions::enable_prevent_default(),
+                move |e| {
+                    let e = e.dyn_ref::<InputEvent>().unwrap();
+                    if e.data().as_deref() == Some(" ") {
+                        e.prevent_default();
+                        link.send_message(Msg::Game(Action::Shuffle));
+                    }
+                },
+            );
+            self.before_input = Some(listener);
+        }
+    }
+
 
     fn view(&self) -> Html {
         fn keyboard_callback(msg: keyboard::Msg) -> Msg {
             match msg {
-                keyboard::Msg::Char(c) => Msg::PushLetter(c),
-                keyboard::Msg::Shuffle => Msg::Shuffle
+                keyboard::Msg::Char(c) => Msg::Game(Action::Push(c)),
+                keyboard::Msg::Shuffle => Msg::Game(Action::Shuffle)
             }
         }
-        let letters = self.letters.iter().map(|letter| {
+        let game = &self.game;
+        let current = game.current_word();
+        // Cells the in-progress word leaves out are dimmed so the dropped letter stands out
+        let cell_class = |letter: char, position: &str| {
+            if current.is_empty() || current.contains(letter) {
+                format!("hive-cell {}", position)
+            } else {
+                format!("hive-cell {} dropped", position)
+            }
+        };
+        let letters = game.letters().iter().map(|letter| {
+            let letter = *letter;
             html! {
-                <svg onclick=self.callback_for(*letter) class="hive-cell outer" viewBox="0 0 120 103.92304845413263">
+                <svg onclick=self.callback_for(letter) onkeydown=self.press_for(move || Msg::Game(Action::Push(letter))) role="button" tabindex="0" aria-label=a11y::cell_label(letter, false) class=cell_class(letter, "outer") viewBox="0 0 120 103.92304845413263">
                     <polygon class="cell-fill" points="0,51.96152422706631 30,0 90,0 120,51.96152422706631 90,103.92304845413263 30,103.92304845413263" stroke="white" stroke-width="7.5">
                     </polygon>
                     <text class="cell-letter" x="50%" y="50%" dy="0.35em">{ letter }</text>
@@ -232,11 +837,12 @@ impl Component for SpellingBee {
 
             }
         }).collect::<Html>();
+        let center_letter = game.center();
         let center = html! {
-                <svg onclick=self.callback_for(self.center) class="hive-cell center" viewBox="0 0 120 103.92304845413263">
+                <svg onclick=self.callback_for(center_letter) onkeydown=self.press_for(move || Msg::Game(Action::Push(center_letter))) role="button" tabindex="0" aria-label=a11y::cell_label(center_letter, true) class=cell_class(center_letter, "center") viewBox="0 0 120 103.92304845413263">
                     <polygon class="cell-fill" points="0,51.96152422706631 30,0 90,0 120,51.96152422706631 90,103.92304845413263 30,103.92304845413263" stroke="white" stroke-width="7.5">
                     </polygon>
-                    <text class="cell-letter" x="50%" y="50%" dy="0.35em">{ self.center }</text>
+                    <text class="cell-letter" x="50%" y="50%" dy="0.35em">{ game.center() }</text>
                 </svg>
 
         };
@@ -249,69 +855,92 @@ impl Component for SpellingBee {
             None => html! { <div class="sb-message-box" /> },
         };
 
-        let current_word = self
-            .current_word
+        let current_word = current
             .chars()
             .map(|ch| {
-                if ch == self.center {
+                if ch == game.center() {
                     html! { <span class="sb-input-bright"> { ch } </span> }
-                } else if !self.letters.contains(&ch) {
+                } else if !game.letters().contains(&ch) {
                     html! { <span class="sb-input-extra"> { ch } </span> }
                 } else {
                     html! { <span> { ch } </span> }
                 }
             })
             .collect::<Html>();
+        let validity = if current.is_empty() {
+            html! { <div class="sb-input-status" /> }
+        } else {
+            match game.check_letters(current) {
+                Some(problem) => html! { <div class="sb-input-status invalid">{ problem.message() }</div> },
+                None => html! { <div class="sb-input-status valid">{ "✓" }</div> },
+            }
+        };
         //let current_word = self.current_word.clone();
-        let words = self
-            .found_words
-            .iter()
-            .map(|word| html! { <li>{word}</li> })
-            .collect::<Html>();
-        let valid_words = &self.wordlist.words;
+        let valid_words = &game.wordlist().words;
         let dots = (0..valid_words.len())
-            .into_iter()
             .map(|i| {
-                if i <= self.found_words.len() {
+                if i <= game.found_words().len() {
                     html! { <span class="sb-progress-dot completed" /> }
                 } else {
                     html! { <span class="sb-progress-dot" /> }
                 }
             })
             .collect::<Html>();
-        let offset = (100.0 / valid_words.len() as f64) * self.found_words.len() as f64;
+        let offset = (100.0 / valid_words.len() as f64) * game.found_words().len() as f64;
+        // A team hive's found list is the pooled one, so this is the team's rank
+        let rank = match self.mode {
+            Mode::Team => format!("Team: {}", game.rank()),
+            Mode::Classic => format!("Classic: {}", game.rank()),
+            _ => game.rank().to_string(),
+        };
         let progress = html! {
             <span role="presentation">
                 <div class="sb-progress" title="Click to see today’s ranks">
-                  <h4 class="sb-progress-rank">{ "Amazing Human/Genius" }</h4>
+                  <h4 class="sb-progress-rank">{ rank }</h4>
                   <div class="sb-progress-bar">
                   <div class="sb-progress-line">
                     <div class="sb-progress-dots">
                         {dots}
                     </div>
             </div>
-            <div class="sb-progress-marker" style={ format!("left: {}%", offset)}><span class="sb-progress-value"> { self.found_words.len() }</span></div></div></div>
+            <div class="sb-progress-marker" style={ format!("left: {}%", offset)}><span class="sb-progress-value"> { game.found_words().len() }</span></div></div></div>
             </span>
         };
         let hidden = if self.wordlist_visible { "wordlist-drawer" } else { "wordlist-drawer hidden" };
         let showhide_text = if self.wordlist_visible { "Hide" } else { "Show" };
         let showhide = html! { <button onclick={self.link.callback(|_|Msg::ToggleWords)}>{ showhide_text }</button> };
+        let reveal = if game.revealed() {
+            reveal::view(game.wordlist(), game.found_words())
+        } else if self.mode.is_shared() {
+            html! {}
+        } else {
+            html! { <button class="reveal-button" onclick={self.link.callback(|_|Msg::Reveal)}>{ "Reveal" }</button> }
+        };
+        let stats = self.mode.keeps_stats().then(|| practice::stats(&self.local_storage, self.mode));
+        let definitions = game
+            .found_words()
+            .iter()
+            .filter_map(|word| Some((word.clone(), game.wordlist().definition(word)?.to_string())))
+            .collect::<HashMap<_, _>>();
         let wordlist = html! {
                     <div class="wordlist-box">
                         <div class="wordlist-heading">
-                            <div class="wordlist-summary">{ format!("You have found {} words", self.found_words.len()) }</div>
+                            <div class="wordlist-summary">{ format!("You have found {} words", game.found_words().len()) }</div>
                             {showhide}
                         </div>
                         <div class={hidden}>
                             <div class="wordlist-window">
-                                <div class="wordlist-pag">
-                                    <div class="sb-wordlist-scroll-anchor" style="left: 0%;"></div>
-                                    <ul class="sb-wordlist-items-pag single">
-                                        {words}
-                                    </ul>
-                                </div>
-                                <div class="sb-kebob"></div>
+                                <FoundList
+                                    words=game.found_words().to_vec()
+                                    grid=game.grid()
+                                    finders=self.race.finders()
+                                    definitions=definitions
+                                    sort=self.settings.sort
+                                    onsort=self.link.callback(Msg::SetSort)
+                                />
                             </div>
+                            { chart::view(game, stats.as_ref()) }
+                            { reveal }
                         </div>
                     </div>
         };
@@ -321,7 +950,7 @@ impl Component for SpellingBee {
                     <div class="sb-progress-box">{ progress }</div>
                     <div class="sb-wordlist-box">
                         <div class="sb-wordlist-heading">
-                            <div class="sb-wordlist-summary">{ format!("You have found {} words", self.found_words.len()) }</div>
+                            <div class="sb-wordlist-summary">{ format!("You have found {} words", game.found_words().len()) }</div>
                         </div>
                         <div class="sb-wordlist-drawer">
                             <div class="sb-wordlist-window">
@@ -351,8 +980,8 @@ impl Component for SpellingBee {
                             </div>
                         </div>
                         <div class="hive-actions">
-                            <div onclick=self.link.callback(|_|Msg::Submit) class="hive-action hive-action__submit sb-touch-button">{ "Enter" }</div>
-                            <div onclick=self.link.callback(|_|Msg::Backspace) class="hive-action hive-action__delete sb-touch-button">{"Delete"}</div>
+                            <div onclick=self.link.callback(|_|Msg::Game(Action::Submit)) class="hive-action hive-action__submit sb-touch-button">{ "Enter" }</div>
+                            <div onclick=self.link.callback(|_|Msg::Game(Action::Backspace)) class="hive-action hive-action__delete sb-touch-button">{"Delete"}</div>
                             <div onclick=self.link.callback(|_|Msg::Keyboard) class="hive-action hive-action__keyboard sb-touch-button">{"Keyboard"}</div>
                         </div>
                     </div>
@@ -361,27 +990,116 @@ impl Component for SpellingBee {
             </div>
         };
         wrap(inner)*/
+        if self.loading {
+            return html! { <div class="container">{ "Loading puzzle…" }</div> };
+        }
+        // Offered on daily puzzles whose hive was also solved by the regular rules
+        let variant = match self.mode {
+            Mode::Daily if game.wordlist().classic.is_some() => html! {
+                <span role="presentation" class="pz-toolbar-button" onclick=self.link.callback(|_|Msg::ToggleClassic)>{ "Classic" }</span>
+            },
+            Mode::Classic => html! {
+                <span role="presentation" class="pz-toolbar-button" onclick=self.link.callback(|_|Msg::ToggleClassic)>{ "Emily’s Bee" }</span>
+            },
+            _ => html! {},
+        };
+        let toolbar = html! {
+            <div class="pz-toolbar-right">
+                { variant }
+                <span role="presentation" class="pz-toolbar-button pz-toolbar-button__yesterday" onclick=self.link.callback(|_|Msg::ToggleYesterday)>{ "Yesterday’s Answers" }</span>
+                <span role="presentation" class="pz-toolbar-button" onclick=self.link.callback(|_|Msg::ToggleCreator)>{ "Create" }</span>
+                <span role="presentation" class="pz-toolbar-button" onclick=self.link.callback(|_|Msg::TogglePractice)>{ "Practice" }</span>
+                <span role="presentation" class="pz-toolbar-button" onclick=self.link.callback(|_|Msg::ToggleRace)>{ "Race" }</span>
+                <span role="presentation" class="pz-toolbar-button" onclick=self.link.callback(|_|Msg::ToggleLeaderboard)>{ "Leaderboard" }</span>
+                <span role="presentation" class="pz-toolbar-button" onclick=self.link.callback(|_|Msg::ToggleBlitz)>{ "Blitz" }</span>
+                <span role="presentation" class="pz-toolbar-button" onclick=self.link.callback(|_|Msg::ToggleHints)>{ "Hints" }</span>
+                <span role="presentation" class="pz-toolbar-button" onclick=self.link.callback(|_|Msg::ToggleSettings)>{ "Settings" }</span>
+            </div>
+        };
+        // The scoreboard stays up for the whole race
+        let race = if self.race_visible || self.mode.is_shared() {
+            self.race.view(&self.link)
+        } else {
+            html! {}
+        };
+        // A blitz round only shows the clock and score, until it ends
+        let status = match &self.blitz {
+            Some(blitz) => self.blitz_view(blitz),
+            None => html! { <>{ wordlist }{ progress }</> },
+        };
+        // Only dated daily puzzles can be checked by the leaderboard server
+        let leaderboard = match (&game.wordlist().date, self.mode) {
+            _ if !self.leaderboard_visible => html! {},
+            (Some(date), Mode::Daily) => html! {
+                <leaderboard::Leaderboard date=date.clone() wordlist=game.wordlist().clone() words=game.found_words().to_vec() />
+            },
+            _ => html! { <div class="leaderboard-box">{ "The leaderboard only counts daily puzzles" }</div> },
+        };
+        let settings = if self.settings_visible {
+            html! { <settings::Panel settings=self.settings.clone() onchange=self.link.callback(Msg::UpdateSettings) /> }
+        } else {
+            html! {}
+        };
+        let keyboard = if self.settings.show_keyboard {
+            html! {
+                <div class="keyboard-footer">
+                    <Keyboard purple={self.purple()} added={game.wordlist().rule.added} grid={game.grid()} layout={self.settings.layout} coverage={self.settings.hints.then(|| game.coverage())} ontype={ self.link.callback(keyboard_callback) } />
+                </div>
+            }
+        } else {
+            html! {}
+        };
+        let creator = if self.creator_visible {
+            html! { <creator::Creator /> }
+        } else {
+            html! {}
+        };
         html! {
             <div class="container">
-                { wordlist }
+                { toolbar }
+                { settings }
+                { self.yesterday_view() }
+                { creator }
+                { self.practice_view() }
+                { self.hints_view() }
+                { race }
+                { leaderboard }
+                { self.blitz_options() }
+                { self.completion_view() }
+                { status }
+                { message }
+                <input
+                    id=HIDDEN_INPUT
+                    class="hidden-input"
+                    type="text"
+                    value=current.to_string()
+                    oninput=self.link.callback(|e: InputData| Msg::Game(Action::SetWord(e.value)))
+                    autocomplete="off"
+                    autocorrect="off"
+                    autocapitalize="none"
+                    spellcheck="false"
+                    enterkeyhint="enter"
+                />
                 <div class="sb-hive-input">
                     <span class="sb-hive-input-content non-empty" style="font-size: 1em;">
                         <span class="">{{ current_word }}</span>
                     </span>
                 </div>
+                { validity }
+                <p id=a11y::DESCRIPTION_ID class="visually-hidden">{ a11y::describe(game) }</p>
+                <div class="visually-hidden" role="status" aria-live="polite" aria-atomic="true">{ &self.announcement }</div>
                 <div class="sb-hive hive-container">
-                    <div class="hive">
+                    <div class="hive" role="group" aria-label="Hive" aria-describedby=a11y::DESCRIPTION_ID>
                         {{ center }}
                         {{ letters }}
                     </div>
                 </div>
                 <div class="hive-actions">
-                    <div onclick=self.link.callback(|_|Msg::Submit) class="hive-action hive-action__submit sb-touch-button">{ "Enter" }</div>
-                    <div onclick=self.link.callback(|_|Msg::Backspace) class="hive-action hive-action__delete sb-touch-button">{"Delete"}</div>
-                </div>
-                <div class="keyboard-footer">
-                    <Keyboard purple={self.purple()} grid={self.grid()} ontype={ self.link.callback(keyboard_callback) } />
+                    <div onclick=self.link.callback(|_|Msg::Game(Action::Submit)) onkeydown=self.press_for(|| Msg::Game(Action::Submit)) role="button" tabindex="0" class="hive-action hive-action__submit sb-touch-button">{ "Enter" }</div>
+                    <div onclick=self.link.callback(|_|Msg::Game(Action::Backspace)) onkeydown=self.press_for(|| Msg::Game(Action::Backspace)) role="button" tabindex="0" class="hive-action hive-action__delete sb-touch-button">{"Delete"}</div>
+                    <div onclick=self.link.callback(|_|Msg::Keyboard) onkeydown=self.press_for(|| Msg::Keyboard) role="button" tabindex="0" class="hive-action hive-action__keyboard sb-touch-button">{"Keyboard"}</div>
                 </div>
+                { keyboard }
             </div>
         }
     }
@@ -0,0 +1,139 @@
+use crate::creator;
+use eb_core::{Difficulty, LetterRule, Stats, Wordlist};
+use yew::web_sys::Storage;
+
+/// Generated hives with fewer answers than this are rerolled
+pub const MIN_ANSWERS: usize = 8;
+
+const DIFFICULTY_KEY: &str = "practice-difficulty";
+const RULE_KEY: &str = "practice-rule";
+const DAILY_STATS_KEY: &str = "stats";
+const PRACTICE_STATS_KEY: &str = "practice-stats";
+const CLASSIC_STATS_KEY: &str = "classic-stats";
+
+/// Daily and archived puzzles share progress and stats; practice hives are thrown away once
+/// rerolled, so only their totals are kept. Classic play of a daily hive keeps progress and
+/// totals of its own. Races and team hives are scored by the server, and blitz rounds keep a
+/// best score per puzzle instead, so all three stay out of both.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    Daily,
+    /// A daily hive under the regular Spelling Bee rules
+    Classic,
+    Practice,
+    Race,
+    Team,
+    Blitz,
+}
+
+impl Mode {
+    /// Played in a room on `eb-server`
+    pub fn is_shared(&self) -> bool {
+        matches!(self, Mode::Race | Mode::Team)
+    }
+
+    /// Progress, reveals and hints are kept between visits
+    pub fn saves_progress(&self) -> bool {
+        matches!(self, Mode::Daily | Mode::Classic)
+    }
+
+    /// Counts towards the running totals, and so gets a completion record
+    pub fn keeps_stats(&self) -> bool {
+        self.stats_key().is_some()
+    }
+
+    fn stats_key(&self) -> Option<&'static str> {
+        match self {
+            Mode::Daily => Some(DAILY_STATS_KEY),
+            Mode::Classic => Some(CLASSIC_STATS_KEY),
+            Mode::Practice => Some(PRACTICE_STATS_KEY),
+            Mode::Race | Mode::Team | Mode::Blitz => None,
+        }
+    }
+}
+
+/// A fresh hive from the bundled dictionary, or `None` if none fit the difficulty
+pub fn generate(difficulty: Difficulty, rule: LetterRule) -> Option<Wordlist> {
+    creator::dictionary().generate(&mut rand::thread_rng(), MIN_ANSWERS, difficulty, rule)
+}
+
+pub fn stored_rule(storage: &Storage) -> LetterRule {
+    storage
+        .get_item(RULE_KEY)
+        .unwrap()
+        .and_then(|name| LetterRule::from_name(&name))
+        .filter(creator::solves)
+        .unwrap_or_default()
+}
+
+pub fn store_rule(storage: &Storage, rule: LetterRule) {
+    storage.set_item(RULE_KEY, &rule.name()).unwrap();
+}
+
+pub fn stored_difficulty(storage: &Storage) -> Difficulty {
+    storage
+        .get_item(DIFFICULTY_KEY)
+        .unwrap()
+        .and_then(|name| Difficulty::from_name(&name))
+        .unwrap_or(Difficulty::Easy)
+}
+
+pub fn store_difficulty(storage: &Storage, difficulty: Difficulty) {
+    storage.set_item(DIFFICULTY_KEY, difficulty.name()).unwrap();
+}
+
+pub fn stats(storage: &Storage, mode: Mode) -> Stats {
+    let json = mode.stats_key().and_then(|key| storage.get_item(key).unwrap());
+    Stats::parse(json.as_deref())
+}
+
+/// Adds a found word to the running totals for `mode`
+pub fn record_find(storage: &Storage, mode: Mode, points: usize, first: bool) {
+    if let Some(key) = mode.stats_key() {
+        let mut stats = stats(storage, mode);
+        stats.record_find(points, first);
+        storage.set_item(key, &stats.to_json()).unwrap();
+    }
+}
+
+/// Counts a finished puzzle towards the totals for `mode`
+pub fn record_completion(storage: &Storage, mode: Mode, seconds: Option<u64>) {
+    if let Some(key) = mode.stats_key() {
+        let mut stats = stats(storage, mode);
+        stats.record_completion(seconds);
+        storage.set_item(key, &stats.to_json()).unwrap();
+    }
+}
+
+/// Counts reaching Genius after `ms` of play towards the totals for `mode`
+pub fn record_genius(storage: &Storage, mode: Mode, ms: u64) {
+    if let Some(key) = mode.stats_key() {
+        let mut stats = stats(storage, mode);
+        stats.record_genius(ms);
+        storage.set_item(key, &stats.to_json()).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::creator::rules;
+    use crate::practice::{generate, MIN_ANSWERS};
+    use eb_core::{Difficulty, LetterRule};
+
+    #[test]
+    fn bundled_dictionary_generates_every_difficulty() {
+        for difficulty in Difficulty::ALL.iter() {
+            let wordlist = generate(*difficulty, LetterRule::EMILY).unwrap();
+            assert!(wordlist.words.len() >= MIN_ANSWERS);
+        }
+    }
+
+    #[test]
+    fn bundled_dictionary_generates_every_rule() {
+        for rule in rules() {
+            let wordlist = generate(Difficulty::Easy, rule).unwrap();
+            assert_eq!(wordlist.rule, rule);
+            assert!(wordlist.words.len() >= MIN_ANSWERS);
+        }
+    }
+}
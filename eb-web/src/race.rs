@@ -0,0 +1,266 @@
+use crate::{archive, Msg as AppMsg, SpellingBee};
+use eb_core::room::{ClientMessage, FoundWord, Phase, RoomKind, RoomState, ServerMessage};
+use eb_core::Wordlist;
+use std::collections::HashMap;
+use yew::format::Text;
+use yew::services::websocket::{WebSocketService, WebSocketStatus, WebSocketTask};
+use yew::{html, ComponentLink, Html, InputData};
+
+/// Where `eb-server` listens when run locally; `?server=` points the app elsewhere
+pub const DEFAULT_SERVER: &str = "ws://localhost:9001";
+
+/// Length of a race created from the lobby
+const RACE_SECONDS: u64 = 180;
+
+pub enum Msg {
+    Name(String),
+    Code(String),
+    Create(RoomKind),
+    Join,
+    Start,
+    Received(Option<ServerMessage>),
+    Status(WebSocketStatus),
+}
+
+/// Lobby and live scoreboard for playing the same hive with others through `eb-server`, either
+/// racing or as a team
+pub struct Race {
+    server: String,
+    name: String,
+    code_input: String,
+    task: Option<WebSocketTask>,
+    /// Create or join request, sent once the connection opens
+    hello: Option<ClientMessage>,
+    code: Option<String>,
+    kind: RoomKind,
+    player: Option<usize>,
+    room: Option<RoomState>,
+    error: Option<String>,
+}
+
+impl Race {
+    /// `?room=` fills in the code to join, so a room can be shared as a link
+    pub fn new(search: &str) -> Race {
+        Race {
+            server: archive::query_param(search, "server").unwrap_or_else(|| DEFAULT_SERVER.to_string()),
+            name: String::new(),
+            code_input: archive::query_param(search, "room").unwrap_or_default(),
+            task: None,
+            hello: None,
+            code: None,
+            kind: RoomKind::Race,
+            player: None,
+            room: None,
+            error: None,
+        }
+    }
+
+    pub fn invited(&self) -> bool {
+        !self.code_input.is_empty()
+    }
+
+    pub fn phase(&self) -> Option<Phase> {
+        self.room.as_ref().map(|room| room.phase)
+    }
+
+    pub fn accepts_words(&self) -> bool {
+        self.phase().is_some_and(|phase| phase.accepts_words())
+    }
+
+    /// A team room's pooled words; always empty in a race
+    pub fn found(&self) -> &[FoundWord] {
+        self.room.as_ref().map_or(&[], |room| &room.found)
+    }
+
+    /// Each pooled word and the name of the teammate who found it
+    pub fn finders(&self) -> HashMap<String, String> {
+        let room = match &self.room {
+            Some(room) => room,
+            None => return HashMap::new(),
+        };
+        room.found
+            .iter()
+            .filter_map(|found| Some((found.word.clone(), room.players.get(found.player)?.name.clone())))
+            .collect()
+    }
+
+    /// Reports a word the local game accepted; the server checks it again before scoring it
+    pub fn submit(&mut self, word: &str) {
+        self.send(ClientMessage::Submit { word: word.to_string() });
+    }
+
+    fn send(&mut self, message: ClientMessage) {
+        if let Some(task) = &mut self.task {
+            task.send(Ok(serde_json::to_string(&message).unwrap()));
+        }
+    }
+
+    fn connect(&mut self, link: &ComponentLink<SpellingBee>, hello: ClientMessage) {
+        let callback = link.callback(|text: Text| {
+            AppMsg::Race(Msg::Received(text.ok().and_then(|text| serde_json::from_str(&text).ok())))
+        });
+        let notification = link.callback(|status| AppMsg::Race(Msg::Status(status)));
+        match WebSocketService::connect_text(&self.server, callback, notification) {
+            Ok(task) => {
+                self.task = Some(task);
+                self.hello = Some(hello);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    /// Returns the room's kind and puzzle once the server has seated this player
+    pub fn update(&mut self, msg: Msg, link: &ComponentLink<SpellingBee>) -> Option<(RoomKind, Wordlist)> {
+        match msg {
+            Msg::Name(name) => self.name = name,
+            Msg::Code(code) => self.code_input = code,
+            Msg::Create(kind) => {
+                let hello = ClientMessage::Create { name: self.name.clone(), seconds: RACE_SECONDS, kind };
+                self.connect(link, hello);
+            }
+            Msg::Join => {
+                let hello = ClientMessage::Join { code: self.code_input.clone(), name: self.name.clone() };
+                self.connect(link, hello);
+            }
+            Msg::Start => self.send(ClientMessage::Start),
+            Msg::Status(WebSocketStatus::Opened) => {
+                if let Some(hello) = self.hello.take() {
+                    self.send(hello);
+                }
+            }
+            Msg::Status(_) => {
+                self.task = None;
+                self.error = Some(format!("Lost the connection to {}", self.server));
+            }
+            Msg::Received(Some(ServerMessage::Joined { code, player, wordlist, kind })) => {
+                self.code = Some(code);
+                self.kind = kind;
+                self.player = Some(player);
+                return Some((kind, wordlist));
+            }
+            Msg::Received(Some(ServerMessage::Room(room))) => self.room = Some(room),
+            Msg::Received(Some(ServerMessage::Error { message })) => self.refused(message),
+            Msg::Received(None) => (),
+        }
+        None
+    }
+
+    /// Shows a server error. One that comes before a seat, such as a wrong code or a race that
+    /// has already started, closes the connection so the lobby can be used again.
+    fn refused(&mut self, message: String) {
+        if self.code.is_none() {
+            self.task = None;
+            self.hello = None;
+        }
+        self.error = Some(message);
+    }
+
+    fn scoreboard(&self, link: &ComponentLink<SpellingBee>) -> Html {
+        let room = match &self.room {
+            Some(room) => room,
+            None => return html! {},
+        };
+        let status = match room.phase {
+            Phase::Waiting if self.player == Some(0) => html! {
+                <button type="button" onclick=link.callback(|_| AppMsg::Race(Msg::Start))>{ "Start the race" }</button>
+            },
+            Phase::Waiting => html! { <span>{ "Waiting for the host to start" }</span> },
+            Phase::Running { remaining } => html! {
+                <span class="race-clock">{ format!("{}:{:02} left", remaining / 60, remaining % 60) }</span>
+            },
+            Phase::Finished => html! { <span>{ "Race over" }</span> },
+            Phase::Open => html! { <span>{ "Every word counts for the whole team" }</span> },
+        };
+        let mut standings = room.players.iter().enumerate().collect::<Vec<_>>();
+        standings.sort_by_key(|(_, score)| std::cmp::Reverse(score.points));
+        let rows = standings.into_iter().map(|(player, score)| {
+            let mut class = String::from("race-player");
+            if Some(player) == self.player {
+                class.push_str(" race-you");
+            }
+            if !score.connected {
+                class.push_str(" race-gone");
+            }
+            html! {
+                <tr class=class>
+                    <td>{ &score.name }</td>
+                    <td>{ format!("{} words", score.words) }</td>
+                    <td>{ format!("{} points", score.points) }</td>
+                </tr>
+            }
+        }).collect::<Html>();
+        html! {
+            <>
+                <div class="race-status">{ status }</div>
+                <table class="race-scores">{ rows }</table>
+            </>
+        }
+    }
+
+    pub fn view(&self, link: &ComponentLink<SpellingBee>) -> Html {
+        let error = match &self.error {
+            Some(error) => html! { <p class="race-error">{ error }</p> },
+            None => html! {},
+        };
+        if let Some(code) = &self.code {
+            let heading = match self.kind {
+                RoomKind::Race => format!("Race {}", code),
+                RoomKind::Team => format!("Team hive {}", code),
+            };
+            return html! {
+                <div class="race-box">
+                    <h3>{ heading }</h3>
+                    { self.scoreboard(link) }
+                    { error }
+                    <a href="?">{ "Leave" }</a>
+                </div>
+            };
+        }
+        let connecting = self.task.is_some();
+        html! {
+            <div class="race-box">
+                <h3>{ "Play together" }</h3>
+                <input
+                    class="race-name"
+                    type="text"
+                    placeholder="Your name"
+                    value=self.name.clone()
+                    oninput=link.callback(|e: InputData| AppMsg::Race(Msg::Name(e.value)))
+                />
+                <div class="race-lobby">
+                    <button type="button" disabled=connecting onclick=link.callback(|_| AppMsg::Race(Msg::Create(RoomKind::Race)))>{ "New race" }</button>
+                    <button type="button" disabled=connecting onclick=link.callback(|_| AppMsg::Race(Msg::Create(RoomKind::Team)))>{ "New team hive" }</button>
+                    <input
+                        class="race-code"
+                        type="text"
+                        placeholder="Room code"
+                        value=self.code_input.clone()
+                        oninput=link.callback(|e: InputData| AppMsg::Race(Msg::Code(e.value)))
+                    />
+                    <button type="button" disabled=connecting onclick=link.callback(|_| AppMsg::Race(Msg::Join))>{ "Join" }</button>
+                </div>
+                { error }
+            </div>
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::race::Race;
+    use eb_core::room::ClientMessage;
+
+    #[test]
+    fn errors_before_joining_reopen_the_lobby() {
+        let mut race = Race::new("?room=ABCD");
+        race.hello = Some(ClientMessage::Join { code: "ABCD".to_string(), name: "Emily".to_string() });
+        race.refused("No room ABCD".to_string());
+        assert!(race.hello.is_none() && race.task.is_none());
+        assert_eq!(race.error.as_deref(), Some("No room ABCD"));
+
+        race.code = Some("ABCD".to_string());
+        race.refused("Unknown word".to_string());
+        assert_eq!(race.code.as_deref(), Some("ABCD"));
+    }
+}
@@ -0,0 +1,111 @@
+use eb_core::{added_letters, dropped_letters, Wordlist};
+use std::collections::{BTreeMap, BTreeSet};
+use yew::{html, Html};
+
+/// Puzzles older than this (measured from the start of their day) show their answers unprompted
+pub const AUTO_REVEAL_MS: f64 = 2.0 * 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Answers that share the same dropped hive letters and added outside letters
+#[derive(Debug, PartialEq)]
+pub struct Group<'a> {
+    pub dropped: BTreeSet<char>,
+    pub added: BTreeSet<char>,
+    pub found: Vec<&'a str>,
+    pub missed: Vec<&'a str>,
+}
+
+pub fn groups<'a>(wordlist: &'a Wordlist, found_words: &[String]) -> Vec<Group<'a>> {
+    let grid = wordlist.to_set();
+    let mut groups: BTreeMap<(BTreeSet<char>, BTreeSet<char>), Group> = BTreeMap::new();
+    for word in &wordlist.words {
+        let dropped = dropped_letters(&grid, word);
+        let added = added_letters(&grid, word);
+        let group = groups.entry((dropped.clone(), added.clone())).or_insert_with(|| Group {
+            dropped,
+            added,
+            found: vec![],
+            missed: vec![],
+        });
+        if found_words.contains(word) {
+            group.found.push(word);
+        } else {
+            group.missed.push(word);
+        }
+    }
+    groups.into_values().collect()
+}
+
+/// `word` with its definition, if it has one, shown on hover and opened on tap
+pub fn defined_word(word: &str, definition: Option<&str>) -> Html {
+    match definition {
+        Some(definition) => html! {
+            <details class="word-definition" title=definition.to_string()>
+                <summary>{ word }</summary>
+                <p>{ definition }</p>
+            </details>
+        },
+        None => html! { word },
+    }
+}
+
+fn word_item(wordlist: &Wordlist, word: &str, class: &'static str) -> Html {
+    let obscurity = match wordlist.metadata.get(word) {
+        Some(info) => html! { <span class="reveal-obscurity" title="Obscurity">{ info.obscurity }</span> },
+        None => html! {},
+    };
+    html! {
+        <li class=class>
+            <span class="reveal-word">{ defined_word(word, wordlist.definition(word)) }</span>
+            <span class="reveal-length">{ word.len() }</span>
+            { obscurity }
+        </li>
+    }
+}
+
+pub fn view(wordlist: &Wordlist, found_words: &[String]) -> Html {
+    let show = |letters: &BTreeSet<char>| if letters.is_empty() { "?".to_string() } else { letters.iter().collect() };
+    let groups = groups(wordlist, found_words).into_iter().map(|group| {
+        let found = group.found.iter().map(|word| word_item(wordlist, word, "reveal-found")).collect::<Html>();
+        let missed = group.missed.iter().map(|word| word_item(wordlist, word, "reveal-missed")).collect::<Html>();
+        html! {
+            <div class="reveal-group">
+                <h4 class="reveal-group-heading">{ format!("−{} +{}", show(&group.dropped), show(&group.added)) }</h4>
+                <ul>{ found }{ missed }</ul>
+            </div>
+        }
+    }).collect::<Html>();
+    let missed = wordlist.words.len() - found_words.iter().filter(|word| wordlist.words.contains(word)).count();
+    html! {
+        <div class="reveal-box">
+            <div class="reveal-summary">{ format!("You missed {} of {} words", missed, wordlist.words.len()) }</div>
+            { groups }
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::reveal::{groups, Group};
+    use eb_core::Wordlist;
+
+    #[test]
+    fn groups_by_dropped_and_added() {
+        let wordlist = Wordlist {
+            center: 'c',
+            outer: "gameok".to_string(),
+            words: vec!["lockage".to_string(), "clockage".to_string(), "smocake".to_string()],
+            date: None,
+            metadata: Default::default(),
+            rule: Default::default(),
+            classic: None,
+        };
+        let groups = groups(&wordlist, &["lockage".to_string()]);
+        assert_eq!(groups.len(), 2);
+        let letters = |group: &Group| (group.dropped.iter().collect::<String>(), group.added.iter().collect::<String>());
+        assert_eq!(letters(&groups[0]), ("g".to_string(), "s".to_string()));
+        assert_eq!(groups[0].missed, vec!["smocake"]);
+        assert_eq!(letters(&groups[1]), ("m".to_string(), "l".to_string()));
+        assert_eq!(groups[1].found, vec!["lockage"]);
+        assert_eq!(groups[1].missed, vec!["clockage"]);
+    }
+}
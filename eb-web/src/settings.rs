@@ -0,0 +1,269 @@
+use crate::keyboard::Layout;
+use crate::theme::Theme;
+use serde::{Deserialize, Serialize};
+use yew::web_sys::Storage;
+use yew::{html, Callback, ChangeData, Component, ComponentLink, Html, Properties, ShouldRender};
+
+const SETTINGS_KEY: &str = "settings";
+
+/// Bumped whenever a change to `Settings` needs stored records to be upgraded in `upgrade`
+pub const VERSION: u32 = 1;
+
+/// Where the settings were kept before they had a record of their own
+const LEGACY_LAYOUT_KEY: &str = "keyboard-layout";
+const LEGACY_HINTS_KEY: &str = "keyboard-hints";
+const LEGACY_THEME_KEY: &str = "theme";
+
+/// How the found list is ordered
+#[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortOrder {
+    #[default]
+    Found,
+    Alphabetical,
+    Longest,
+}
+
+impl SortOrder {
+    pub const ALL: [SortOrder; 3] = [SortOrder::Found, SortOrder::Alphabetical, SortOrder::Longest];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SortOrder::Found => "found",
+            SortOrder::Alphabetical => "alphabetical",
+            SortOrder::Longest => "longest",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortOrder::Found => "Order found",
+            SortOrder::Alphabetical => "A to Z",
+            SortOrder::Longest => "Longest first",
+        }
+    }
+
+    pub fn sort<'a>(&self, words: &'a [String]) -> Vec<&'a String> {
+        let mut sorted = words.iter().collect::<Vec<_>>();
+        match self {
+            SortOrder::Found => (),
+            SortOrder::Alphabetical => sorted.sort(),
+            SortOrder::Longest => sorted.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b))),
+        }
+        sorted
+    }
+}
+
+/// Everything the player can change, kept as one JSON record. Fields missing from an older
+/// record take their defaults, so only changes in meaning need a new `VERSION`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    pub layout: Layout,
+    /// The on-screen keyboard under the hive, for phones
+    pub show_keyboard: bool,
+    pub theme: Theme,
+    /// Remaining answers per added letter on the keyboard
+    pub hints: bool,
+    pub sort: SortOrder,
+    pub confirm_reveal: bool,
+    /// Whether puzzles a couple of days old show their answers without being asked
+    pub reveal_old_puzzles: bool,
+    /// Yesterday's answers stay covered until asked for
+    pub cover_yesterday: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            version: VERSION,
+            layout: Layout::default(),
+            show_keyboard: true,
+            theme: Theme::default(),
+            hints: false,
+            sort: SortOrder::default(),
+            confirm_reveal: true,
+            reveal_old_puzzles: true,
+            cover_yesterday: false,
+        }
+    }
+}
+
+impl Settings {
+    /// Reads the stored record, or builds one from the old separate keys the first time
+    pub fn load(storage: &Storage) -> Settings {
+        let record = storage.get_item(SETTINGS_KEY).unwrap();
+        match record.as_deref().and_then(Settings::parse) {
+            Some(settings) => settings,
+            None => {
+                let settings = Settings::from_legacy(|key| storage.get_item(key).unwrap());
+                settings.save(storage);
+                settings
+            }
+        }
+    }
+
+    pub fn save(&self, storage: &Storage) {
+        storage.set_item(SETTINGS_KEY, &serde_json::to_string(self).unwrap()).unwrap();
+    }
+
+    fn parse(json: &str) -> Option<Settings> {
+        serde_json::from_str::<Settings>(json).ok().map(Settings::upgrade)
+    }
+
+    /// Brings a record written by an older version up to date
+    fn upgrade(mut self) -> Settings {
+        // Version 1 is the first; later versions add their steps here
+        self.version = VERSION;
+        self
+    }
+
+    fn from_legacy(get: impl Fn(&str) -> Option<String>) -> Settings {
+        let defaults = Settings::default();
+        Settings {
+            layout: get(LEGACY_LAYOUT_KEY)
+                .and_then(|name| Layout::from_name(&name))
+                .unwrap_or(defaults.layout),
+            hints: get(LEGACY_HINTS_KEY).as_deref() == Some("on"),
+            theme: get(LEGACY_THEME_KEY)
+                .and_then(|name| Theme::from_name(&name))
+                .unwrap_or(defaults.theme),
+            ..defaults
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub settings: Settings,
+    pub onchange: Callback<Settings>,
+}
+
+/// The settings dialog. It keeps nothing itself: every change goes up as a whole new record.
+pub struct Panel {
+    props: Props,
+    link: ComponentLink<Self>,
+}
+
+impl Panel {
+    fn checkbox(&self, label: &str, checked: bool, toggle: fn(&mut Settings)) -> Html {
+        let settings = self.props.settings.clone();
+        let onclick = self.link.callback(move |_| {
+            let mut settings = settings.clone();
+            toggle(&mut settings);
+            settings
+        });
+        html! {
+            <label class="settings-row">
+                <input type="checkbox" checked=checked onclick=onclick />
+                { label }
+            </label>
+        }
+    }
+
+    fn select<T: Copy + PartialEq + 'static>(
+        &self,
+        label: &str,
+        choices: &[T],
+        current: T,
+        describe: fn(&T) -> (&'static str, &'static str),
+        set: fn(&mut Settings, T),
+    ) -> Html {
+        let options = choices.iter().map(|choice| {
+            let (name, text) = describe(choice);
+            html! { <option value=name selected={*choice == current}>{ text }</option> }
+        }).collect::<Html>();
+        let settings = self.props.settings.clone();
+        let choices = choices.to_vec();
+        let onchange = self.link.batch_callback(move |e: ChangeData| match e {
+            ChangeData::Select(select) => {
+                let choice = choices.iter().find(|choice| describe(choice).0 == select.value())?;
+                let mut settings = settings.clone();
+                set(&mut settings, *choice);
+                Some(settings)
+            }
+            _ => None,
+        });
+        html! {
+            <label class="settings-row">
+                { label }
+                <select onchange=onchange>{ options }</select>
+            </label>
+        }
+    }
+}
+
+impl Component for Panel {
+    type Message = Settings;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Panel { props, link }
+    }
+
+    fn update(&mut self, settings: Self::Message) -> ShouldRender {
+        self.props.onchange.emit(settings);
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        let changed = self.props != props;
+        self.props = props;
+        changed
+    }
+
+    fn view(&self) -> Html {
+        let settings = &self.props.settings;
+        html! {
+            <div class="settings-box" role="dialog" aria-label="Settings">
+                <h3>{ "Settings" }</h3>
+                { self.select("Theme", &Theme::ALL, settings.theme, |theme| (theme.name(), theme.label()), |settings, theme| settings.theme = theme) }
+                { self.checkbox("On-screen keyboard", settings.show_keyboard, |settings| settings.show_keyboard = !settings.show_keyboard) }
+                { self.select("Keyboard layout", &Layout::ALL, settings.layout, |layout| (layout.name(), layout.label()), |settings, layout| settings.layout = layout) }
+                { self.checkbox("Letter hints on the keyboard", settings.hints, |settings| settings.hints = !settings.hints) }
+                { self.select("Sort found words", &SortOrder::ALL, settings.sort, |order| (order.name(), order.label()), |settings, order| settings.sort = order) }
+                { self.checkbox("Ask before revealing answers", settings.confirm_reveal, |settings| settings.confirm_reveal = !settings.confirm_reveal) }
+                { self.checkbox("Show answers to old puzzles", settings.reveal_old_puzzles, |settings| settings.reveal_old_puzzles = !settings.reveal_old_puzzles) }
+                { self.checkbox("Cover yesterday’s answers", settings.cover_yesterday, |settings| settings.cover_yesterday = !settings.cover_yesterday) }
+            </div>
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::keyboard::Layout;
+    use crate::settings::{Settings, SortOrder, VERSION};
+    use crate::theme::Theme;
+
+    #[test]
+    fn records_fill_in_missing_fields() {
+        let settings = Settings::parse(r#"{"version":1,"layout":"abc","hints":true}"#).unwrap();
+        assert_eq!(settings.layout, Layout::Alphabetical);
+        assert!(settings.hints);
+        assert!(settings.confirm_reveal);
+        let stored = serde_json::to_string(&settings).unwrap();
+        assert_eq!(Settings::parse(&stored), Some(settings));
+        assert_eq!(Settings::parse("not json"), None);
+    }
+
+    #[test]
+    fn legacy_keys_carry_over() {
+        let settings = Settings::from_legacy(|key| match key {
+            "keyboard-layout" => Some("dvorak".into()),
+            "keyboard-hints" => Some("on".into()),
+            "theme" => Some("high-contrast".into()),
+            _ => None,
+        });
+        assert_eq!((settings.layout, settings.hints, settings.theme), (Layout::Dvorak, true, Theme::HighContrast));
+        assert_eq!(settings.version, VERSION);
+    }
+
+    #[test]
+    fn sort_orders() {
+        let words = vec!["mock".to_string(), "lockage".to_string(), "gamecock".to_string(), "cake".to_string()];
+        assert_eq!(SortOrder::Alphabetical.sort(&words), vec!["cake", "gamecock", "lockage", "mock"]);
+        assert_eq!(SortOrder::Longest.sort(&words), vec!["gamecock", "lockage", "cake", "mock"]);
+    }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+/// Colour schemes from `themes.css`. `System` leaves the choice between light and dark to
+/// `prefers-color-scheme`.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+    HighContrast,
+    Colorblind,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 5] = [
+        Theme::System,
+        Theme::Light,
+        Theme::Dark,
+        Theme::HighContrast,
+        Theme::Colorblind,
+    ];
+
+    /// Stable identifier, used as the `<option>` value, in the settings record and as the page's
+    /// `data-theme`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::System => "system",
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::HighContrast => "high-contrast",
+            Theme::Colorblind => "colorblind",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Theme::System => "System",
+            Theme::Light => "Light",
+            Theme::Dark => "Dark",
+            Theme::HighContrast => "High contrast",
+            Theme::Colorblind => "Colorblind-safe",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Theme> {
+        Theme::ALL.iter().copied().find(|theme| theme.name() == name)
+    }
+
+    /// Switches the page over by setting `data-theme` on the root element, or removing it for
+    /// `System`
+    pub fn apply(&self) {
+        let root = match yew::utils::document().document_element() {
+            Some(root) => root,
+            None => return,
+        };
+        let _ = match self {
+            Theme::System => root.remove_attribute("data-theme"),
+            theme => root.set_attribute("data-theme", theme.name()),
+        };
+    }
+}
@@ -0,0 +1,114 @@
+// Service worker that lets the game run offline.
+//
+// Trunk fingerprints the wasm, its JS glue and the stylesheets, so their names are read out of
+// index.html at install time rather than listed here. Puzzle files are fetched ahead of time for
+// the next few days; the ones that aren't published yet are simply skipped and retried later.
+const CACHE = 'eb-v1';
+const PREFETCH_DAYS = 3;
+const STATIC = [
+  'manifest.webmanifest',
+  'icons/icon-192.png',
+  'icons/icon-512.png',
+  'icons/icon.svg',
+  'fonts/franklin/franklin-normal-300.woff2',
+  'fonts/franklin/franklin-normal-500.woff2',
+  'fonts/franklin/franklin-normal-700.woff2',
+];
+const ASSET = /(?:href|src|from)\s*=?\s*["']([^"']+\.(?:css|wasm|js))["']/g;
+
+function localDate(offset) {
+  const date = new Date();
+  date.setDate(date.getDate() + offset);
+  const pad = n => String(n).padStart(2, '0');
+  return `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}`;
+}
+
+async function prefetchPuzzles(cache) {
+  for (let offset = 0; offset <= PREFETCH_DAYS; offset++) {
+    const url = `word-lists/${localDate(offset)}.json`;
+    if (await cache.match(url)) {
+      continue;
+    }
+    try {
+      const response = await fetch(url, { cache: 'no-cache' });
+      if (response.ok) {
+        await cache.put(url, response);
+      }
+    } catch (_offline) {
+      return;
+    }
+  }
+}
+
+self.addEventListener('install', event => {
+  event.waitUntil((async () => {
+    const cache = await caches.open(CACHE);
+    const index = await fetch('./', { cache: 'no-cache' });
+    const html = await index.clone().text();
+    await cache.put('./', index);
+    const assets = [...html.matchAll(ASSET)].map(match => match[1]);
+    await cache.addAll([...new Set([...assets, ...STATIC])]);
+    await prefetchPuzzles(cache);
+  })());
+  self.skipWaiting();
+});
+
+self.addEventListener('activate', event => {
+  event.waitUntil((async () => {
+    for (const name of await caches.keys()) {
+      if (name !== CACHE) {
+        await caches.delete(name);
+      }
+    }
+    await self.clients.claim();
+  })());
+});
+
+// Network first, so new deploys and newly published puzzles show up as soon as we're online
+async function networkFirst(request, key) {
+  const cache = await caches.open(CACHE);
+  try {
+    const response = await fetch(request);
+    if (response.ok) {
+      await cache.put(key, response.clone());
+    }
+    return response;
+  } catch (offline) {
+    const cached = await cache.match(key, { ignoreSearch: true });
+    if (cached) {
+      return cached;
+    }
+    throw offline;
+  }
+}
+
+// Fingerprinted assets and fonts never change under the same URL
+async function cacheFirst(request) {
+  const cache = await caches.open(CACHE);
+  const cached = await cache.match(request);
+  if (cached) {
+    return cached;
+  }
+  const response = await fetch(request);
+  if (response.ok || response.type === 'opaque') {
+    await cache.put(request, response.clone());
+  }
+  return response;
+}
+
+self.addEventListener('fetch', event => {
+  const request = event.request;
+  if (request.method !== 'GET') {
+    return;
+  }
+  const url = new URL(request.url);
+  const sameOrigin = url.origin === self.location.origin;
+  if (request.mode === 'navigate') {
+    event.respondWith(networkFirst(request, './'));
+    event.waitUntil(caches.open(CACHE).then(prefetchPuzzles));
+  } else if (sameOrigin && url.pathname.includes('/word-lists/')) {
+    event.respondWith(networkFirst(request, request));
+  } else if (sameOrigin || request.destination === 'font') {
+    event.respondWith(cacheFirst(request));
+  }
+});
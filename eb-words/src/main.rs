@@ -1,7 +1,7 @@
 use structopt::StructOpt;
-use std::collections::{HashSet};
 use std::error::Error;
-use serde::Serialize;
+use std::path::PathBuf;
+use eb_core::{Definitions, Dictionary, LetterRule};
 use reqwest::Url;
 use scraper::{Html, Selector};
 use time::macros::offset;
@@ -13,29 +13,65 @@ struct Opt {
     #[structopt(short = "o", long = "obscurity", default_value = "50")]
     max_obscurity: usize,
 
+    #[allow(dead_code)]
     #[structopt(short = "c", long = "center")]
     center_letter: Option<char>,
 
+    #[allow(dead_code)]
     #[structopt(short = "w", long = "word")]
     base_word: Option<String>,
 
+    /// Write the compact dictionary eb-web bundles for puzzle creation here, instead of a puzzle
+    #[structopt(long = "dictionary", parse(from_os_str))]
+    dictionary: Option<PathBuf>,
+
+    /// Dictionary files to define the answers from: WordNet `data.*` files, or `.tsv` files of
+    /// `word<tab>definition` lines. Can be given more than once.
+    #[structopt(long = "definitions", parse(from_os_str))]
+    definitions: Vec<PathBuf>,
+
+    /// The rule the puzzle is solved by: emily, emily-squared or classic
+    #[structopt(long = "rule", default_value = "emily", parse(try_from_str = parse_rule))]
+    rule: LetterRule,
+
 }
 
-fn is_emily_word_for(sb_word: &HashSet<char>, center_letter: char, possible: impl Iterator<Item=char>) -> bool {
-    let possible = possible.collect::<HashSet::<_>>();
-    possible.contains(&center_letter) && sb_word.difference(&possible).count() == 1 && possible.difference(&sb_word).count() == 1
+fn parse_rule(name: &str) -> Result<LetterRule, String> {
+    LetterRule::from_name(name).ok_or_else(|| format!("unknown rule {}", name))
 }
 
-#[derive(Serialize)]
-struct Output {
-    center: char,
-    outer: String,
-    words: Vec<String>
+/// Every word up to `max_obscurity`; a word's obscurity is the SCOWL size of the smallest list it appears in
+fn load_dictionary(max_obscurity: usize) -> Dictionary {
+    let mut dictionary = Dictionary::default();
+    for obscurity in (10..=max_obscurity).step_by(5) {
+        match std::fs::read_to_string(format!("wordlists/english-words.{}", obscurity)) {
+            Ok(file) => {
+                eprintln!("Level: {}", obscurity);
+                dictionary.add_level(obscurity, &file);
+            },
+            Err(_no_file) => {
+            }
+        }
+    }
+    dictionary
+}
+
+fn load_definitions(paths: &[PathBuf]) -> std::io::Result<Definitions> {
+    let mut definitions = Definitions::default();
+    for path in paths {
+        let file = std::fs::read_to_string(path)?;
+        if path.extension().is_some_and(|extension| extension == "tsv") {
+            definitions.add_tsv(&file);
+        } else {
+            definitions.add_wordnet(&file);
+        }
+    }
+    Ok(definitions)
 }
 
 async fn scrape(date: &str) -> (char, String) {
     eprintln!("loading page for {}", date);
-    let uri: Url = format!("https://www.nytimes.com/{date}/crosswords/spelling-bee-forum.html", date = &date).parse().expect("valid uri");
+    let uri: Url = format!("https://www.nytimes.com/{date}/crosswords/spelling-bee-forum.html", date = date).parse().expect("valid uri");
     let page = reqwest::Client::new().get(uri).send().await.expect("failed to load URI");
     assert!(page.status().is_success());
     let html_str = page.bytes().await.expect("failed to load data");
@@ -55,55 +91,42 @@ async fn scrape(date: &str) -> (char, String) {
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>>{
     let opt = Opt::from_args();
+    if let Some(path) = &opt.dictionary {
+        std::fs::write(path, load_dictionary(opt.max_obscurity).to_compact())?;
+        return Ok(());
+    }
     let today = OffsetDateTime::now_utc().to_offset(offset!(-5));
     let today = format!("{}/{:02}/{:02}", today.year(), u8::from(today.month()), today.day());
     let (center, letters) = scrape(&today).await;
 
-    let mut base_word = letters.chars().collect::<HashSet<_>>();
-    base_word.insert(center);
-    let mut words = vec![];
-    for obscurity in (10..=opt.max_obscurity).step_by(5) {
-        match std::fs::read_to_string(format!("wordlists/english-words.{}", obscurity)) {
-            Ok(file) => {
-                eprintln!("Level: {}", obscurity);
-                for word in file.lines() {
-                    if !word.chars().all(|c|c.is_alphabetic()) {
-                        continue;
-                    }
-                    let chars = word.trim().chars().filter(|c|c.is_alphabetic());
-                    if is_emily_word_for(&base_word, center, chars) {
-                        eprintln!("{}", word);
-                        words.push(word.to_string());
-                    }
-                }
-            },
-            Err(_no_file) => {
-            }
+    let dictionary = load_dictionary(opt.max_obscurity);
+    let mut output = dictionary.solve_with(center, &letters, opt.rule);
+    for word in &output.words {
+        eprintln!("{}", word);
+    }
+    // A classic version of the hive is offered alongside, unless that's the puzzle itself
+    let mut classic = (opt.rule != LetterRule::CLASSIC).then(|| dictionary.solve_with(center, &letters, LetterRule::CLASSIC));
+    if let Some(classic) = &classic {
+        eprintln!("{} classic answers", classic.words.len());
+    }
+    if !opt.definitions.is_empty() {
+        let definitions = load_definitions(&opt.definitions)?;
+        let defined = definitions.attach(&mut output);
+        eprintln!("Defined {} of {} answers", defined, output.words.len());
+        if let Some(classic) = &mut classic {
+            definitions.attach(classic);
         }
     }
-    let output = Output {
-        center,
-        outer: letters,
-        words
-    };
+    let today = today.replace('/', "-");
+    output.date = Some(today.clone());
+    output.classic = classic.map(|mut classic| {
+        classic.date = Some(today.clone());
+        Box::new(classic)
+    });
     println!("{:?}", std::env::current_dir());
-    let today = today.replace("/", "-");
     std::fs::write(format!("../eb-web/word-lists/{}.json", today), serde_json::to_string(&output)?)?;
     std::fs::remove_file("../eb-web/word-lists/today.json")?;
     std::fs::write("../eb-web/word-lists/today.json", serde_json::to_string(&output)?)?;
     println!("done!");
     Ok(())
 }
-
-#[cfg(test)]
-mod test {
-    use crate::is_emily_word_for;
-
-    #[test]
-    fn emily_words() {
-        let base = "gamecock".chars().collect();
-        assert!(is_emily_word_for(&base, 'c', "lockage".chars()));
-        assert!(!is_emily_word_for(&base, 'c', "bloop".chars()))
-    }
-
-}